@@ -0,0 +1,85 @@
+//! Сравнение поиска столкновений перебором и через пространственную сетку:
+//! для каждого из 100, 300 и 500 кругов ищутся все пересекающиеся с ним.
+//! Сетка, как и в игре, заполняется заново в каждом замере.
+//!
+//! Запуск: `cargo bench --bench spatial`.
+
+use asteroids::rng::Rng;
+use asteroids::spatial::SpatialGrid;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use macroquad::prelude::Vec2;
+
+/// Случайные круги, разбросанные по области 1000 на 1000 пикселей.
+fn circles(count: usize) -> Vec<(Vec2, f32)> {
+    let mut rng = Rng::new(42);
+    (0..count)
+        .map(|_| {
+            let position = Vec2::new(rng.gen_range(0.0, 1000.0), rng.gen_range(0.0, 1000.0));
+            (position, rng.gen_range(25.0, 100.0))
+        })
+        .collect()
+}
+
+/// Квадрат, описанный вокруг круга.
+fn bounds((position, radius): (Vec2, f32)) -> (Vec2, Vec2) {
+    let offset = Vec2::new(radius, radius);
+    (position - offset, position + offset)
+}
+
+/// Пересекается ли круг с прямоугольником.
+fn intersects((position, radius): (Vec2, f32), min: Vec2, max: Vec2) -> bool {
+    let nearest = position.max(min).min(max);
+    (position - nearest).length() < radius
+}
+
+/// Число пересечений, найденных перебором всех пар кругов.
+fn brute_force(circles: &[(Vec2, f32)]) -> usize {
+    circles
+        .iter()
+        .map(|&circle| {
+            let (min, max) = bounds(circle);
+            circles
+                .iter()
+                .filter(|&&other| intersects(other, min, max))
+                .count()
+        })
+        .sum()
+}
+
+/// Число пересечений, найденных через сетку.
+fn with_grid(grid: &mut SpatialGrid, circles: &[(Vec2, f32)]) -> usize {
+    grid.clear();
+    for (index, &(position, radius)) in circles.iter().enumerate() {
+        grid.insert(index, position, radius);
+    }
+    circles
+        .iter()
+        .map(|&circle| {
+            let (min, max) = bounds(circle);
+            grid.query_region(min, max)
+                .filter(|&index| intersects(circles[index], min, max))
+                .count()
+        })
+        .sum()
+}
+
+fn collisions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collisions");
+    for count in [100, 300, 500] {
+        let circles = circles(count);
+        let mut grid = SpatialGrid::new(128.0);
+        assert_eq!(brute_force(&circles), with_grid(&mut grid, &circles));
+        group.bench_with_input(
+            BenchmarkId::new("brute_force", count),
+            &circles,
+            |b, circles| b.iter(|| brute_force(circles)),
+        );
+        group.bench_with_input(BenchmarkId::new("grid", count), &circles, |b, circles| {
+            b.iter(|| with_grid(&mut grid, circles))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, collisions);
+criterion_main!(benches);
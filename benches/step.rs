@@ -0,0 +1,42 @@
+//! Замер времени одного шага игрового мира (движение и столкновения, без отображения)
+//! при 50, 200 и 1000 астероидах на экране.
+//!
+//! Запуск: `cargo bench --bench step`.
+
+use asteroids::config::GameConfig;
+use asteroids::difficulty::Difficulty;
+use asteroids::game::Game;
+use asteroids::input::Input;
+use asteroids::replay::FrameInput;
+use asteroids::ship::FlightMode;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use macroquad::prelude::Vec2;
+
+fn step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step");
+    for count in [50, 200, 1000] {
+        // Каждый замер начинается с новой игры, чтобы астероиды не успевали разлететься.
+        let game = || {
+            Game::simulation(
+                FlightMode::Classic,
+                false,
+                Difficulty::Normal,
+                GameConfig::default(),
+                42,
+                Vec2::new(800.0, 600.0),
+            )
+            .with_asteroid_field(count)
+        };
+        let frame = FrameInput {
+            input: Input::default(),
+            elapsed_time: Game::STEP,
+        };
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, _| {
+            b.iter_batched_ref(game, |game| game.advance(frame), BatchSize::LargeInput)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
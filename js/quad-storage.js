@@ -0,0 +1,41 @@
+"use strict";
+
+// Локальное хранилище браузера (Web Storage) для crate quad-storage-sys,
+// которым игра пользуется с флагом `web-storage`. Строки передаются через
+// объекты sapp_jsutils.js, поэтому он должен быть подключён раньше.
+
+function quad_storage_register_plugin(importObject) {
+    importObject.env.quad_storage_length = function () {
+        return window.localStorage.length;
+    }
+
+    importObject.env.quad_storage_has_key = function (i) {
+        return window.localStorage.key(i) !== null ? 1 : 0;
+    }
+
+    importObject.env.quad_storage_key = function (i) {
+        return js_object(window.localStorage.key(i));
+    }
+
+    importObject.env.quad_storage_has_value = function (key) {
+        return window.localStorage.getItem(get_js_object(key)) !== null ? 1 : 0;
+    }
+
+    importObject.env.quad_storage_get = function (key) {
+        return js_object(window.localStorage.getItem(get_js_object(key)));
+    }
+
+    importObject.env.quad_storage_set = function (key, value) {
+        window.localStorage.setItem(get_js_object(key), get_js_object(value));
+    }
+
+    importObject.env.quad_storage_remove = function (key) {
+        window.localStorage.removeItem(get_js_object(key));
+    }
+
+    importObject.env.quad_storage_clear = function () {
+        window.localStorage.clear();
+    }
+}
+
+miniquad_add_plugin({ register_plugin: quad_storage_register_plugin, version: 1, name: "quad_storage" });
@@ -0,0 +1,75 @@
+//! Текстуры корабля, астероидов и фона.
+//!
+//! Текстуры необязательны: если файла нет или его не удалось загрузить,
+//! объект отображается фигурами, как без текстур.
+
+use macroquad::prelude::*;
+use std::sync::OnceLock;
+
+/// Файл текстуры корабля. Нос корабля на ней смотрит вверх.
+const SHIP_FILE: &str = "assets/ship.png";
+/// Файл текстуры астероида. Астероид вписан в текстуру.
+const ASTEROID_FILE: &str = "assets/asteroid.png";
+/// Файл фона, растягиваемого на всё окно.
+const BACKGROUND_FILE: &str = "assets/background.png";
+
+/// Загруженные текстуры.
+#[derive(Clone, Copy, Default)]
+pub struct Textures {
+    pub ship: Option<Texture2D>,
+    pub asteroid: Option<Texture2D>,
+    pub background: Option<Texture2D>,
+}
+
+/// Текстуры игры. Пока они не загружены, текстур нет.
+static TEXTURES: OnceLock<Textures> = OnceLock::new();
+
+/// Загрузка текстур из каталога `assets`. Ошибка загрузки не прерывает игру.
+pub async fn load() {
+    let textures = Textures {
+        ship: load_optional(SHIP_FILE).await,
+        asteroid: load_optional(ASTEROID_FILE).await,
+        background: load_optional(BACKGROUND_FILE).await,
+    };
+    let _ = TEXTURES.set(textures);
+}
+
+/// Загруженные текстуры.
+pub fn textures() -> Textures {
+    TEXTURES.get().copied().unwrap_or_default()
+}
+
+/// Отображение текстуры `texture` в прямоугольнике размером `size` с центром `center`,
+/// повёрнутом на угол `rotation` вокруг центра, с оттенком `color`.
+pub fn draw_centered(texture: Texture2D, center: Vec2, size: Vec2, rotation: f32, color: Color) {
+    let corner = center - size / 2.0;
+    let params = DrawTextureParams {
+        dest_size: Some(size),
+        rotation,
+        ..Default::default()
+    };
+    draw_texture_ex(texture, corner.x, corner.y, color, params);
+}
+
+/// Экран загрузки, показываемый, пока загружаются звуки, шрифт и текстуры.
+/// Шрифт ещё не загружен, поэтому текст выводится стандартным шрифтом.
+pub fn draw_loading_screen() {
+    const TEXT: &str = "Loading...";
+    const FONT_SIZE: f32 = 32.0;
+    clear_background(BLACK);
+    let size = measure_text(TEXT, None, FONT_SIZE as _, 1.0);
+    let x = (screen_width() - size.width) / 2.0;
+    let y = (screen_height() + size.height) / 2.0;
+    draw_text(TEXT, x, y, FONT_SIZE, WHITE);
+}
+
+/// Загрузка текстуры. Если загрузить не удалось, то текстуры нет.
+async fn load_optional(path: &str) -> Option<Texture2D> {
+    match load_texture(path).await {
+        Ok(texture) => Some(texture),
+        Err(err) => {
+            warn!("Failed to load texture {}: {}", path, err);
+            None
+        }
+    }
+}
@@ -0,0 +1,583 @@
+//! Астероиды.
+
+use crate::assets;
+use crate::config::GameConfig;
+use crate::difficulty::{Difficulty, Progression};
+use crate::rng::Rng;
+use crate::theme::Theme;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Состояние астероида. Не занимает памяти в куче, поэтому хранится в пуле без аллокаций.
+#[derive(Serialize, Deserialize)]
+pub struct Asteroid {
+    position: Vec2,
+    /// Положение перед последним шагом игрового мира, для плавного отображения между шагами.
+    prev_position: Vec2,
+    speed: Vec2,
+    radius: f32,
+    /// Форма астероида: расстояния от центра до вершин многоугольника в долях радиуса.
+    /// Вершины расположены через равные углы.
+    shape: [f32; Self::VERTEX_COUNT],
+    /// Угол поворота астероида в радианах.
+    rotation: f32,
+    /// Скорость вращения астероида в радианах в секунду.
+    angular_velocity: f32,
+    /// Сколько ещё попаданий пуль выдержит астероид.
+    health: u32,
+    /// Пролёт астероида рядом с кораблём.
+    near_miss: NearMiss,
+    /// Оставшееся время вспышки после пролёта рядом с кораблём.
+    flash: f32,
+}
+
+/// Пролёт астероида рядом с кораблём.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+enum NearMiss {
+    /// Астероид ещё не подлетал к кораблю.
+    Far,
+    /// Астероид пролетает рядом с кораблём прямо сейчас.
+    Close,
+    /// Пролёт уже засчитан либо астероид задел корабль. Больше очков он не принесёт.
+    Done,
+}
+
+impl Asteroid {
+    // Параметры астероидов. Размеры и скорость новых астероидов задаёт уровень сложности,
+    // а скорость вращения и размер осколков - настройки игровой механики.
+    /// Насколько далеко за краем экрана астероид забывается.
+    pub const BOUNDS_MARGIN: f32 = 300.0;
+    /// Число вершин многоугольника, которым отображается астероид.
+    const VERTEX_COUNT: usize = 12;
+    /// Наименьшее расстояние от центра до вершины в долях радиуса.
+    const MIN_VERTEX_DISTANCE: f32 = 0.7;
+    /// Каждые столько пикселей радиуса добавляют астероиду одно попадание, которое он выдерживает.
+    const HEALTH_RADIUS: f32 = 40.0;
+    /// Яркость астероида, у которого осталось одно попадание, относительно целого.
+    const DAMAGED_BRIGHTNESS: f32 = 0.5;
+    /// Длительность вспышки астероида, пролетевшего рядом с кораблём.
+    const FLASH_DURATION: f32 = 0.3;
+    /// За сколько секунд до появления астероида на экране у верхнего края показывается
+    /// предупреждение.
+    const WARNING_TIME: f32 = 0.5;
+    /// Наибольшее число колец бронированного астероида: больше не помещается.
+    const MAX_RINGS: u32 = 4;
+
+    /// Высота, на которой появляются астероиды: так высоко над экраном, что даже
+    /// самый большой астероид уровня сложности `difficulty` появляется невидимым.
+    pub fn spawn_y(difficulty: Difficulty) -> f32 {
+        -2.0 * difficulty.max_radius()
+    }
+
+    /// Создание астероида со случайными параметрами, взятыми из генератора `rng`.
+    /// Размеры и скорость астероида зависят от уровня сложности `difficulty`
+    /// и её нарастания `progression`, скорость вращения - настройками `config`,
+    /// а `width` - ширина экрана, над которой появляется астероид.
+    pub fn new(
+        rng: &mut Rng,
+        difficulty: Difficulty,
+        progression: Progression,
+        config: &GameConfig,
+        width: f32,
+    ) -> Self {
+        // Располагаем астероид случайно, немного выше видимого экрана.
+        let x = rng.gen_range(0.0, width);
+        let y = Self::spawn_y(difficulty);
+
+        // Задаём случайную скорость астероиду.
+        let max_speed = progression.max_speed(difficulty, config);
+        let speed_x = rng.gen_range(0.0, max_speed);
+        let speed_y = rng.gen_range(0.0, max_speed);
+
+        let radius = progression.radius(rng, difficulty, config);
+        Self::with_radius(
+            rng,
+            config,
+            Vec2::new(x, y),
+            Vec2::new(speed_x, speed_y),
+            radius,
+        )
+    }
+
+    /// Создание астероида в точке `position` со скоростью `speed`.
+    /// Размер, форма и вращение астероида, как и у [`Asteroid::new`], случайные.
+    pub fn with_motion(
+        rng: &mut Rng,
+        difficulty: Difficulty,
+        config: &GameConfig,
+        position: Vec2,
+        speed: Vec2,
+    ) -> Self {
+        let radius = Progression::START.radius(rng, difficulty, config);
+        Self::with_radius(rng, config, position, speed, radius)
+    }
+
+    /// Создание астероида радиусом `radius` в точке `position` со скоростью `speed`.
+    /// Форма и вращение астероида случайные.
+    fn with_radius(
+        rng: &mut Rng,
+        config: &GameConfig,
+        position: Vec2,
+        speed: Vec2,
+        radius: f32,
+    ) -> Self {
+        // Делаем астероид неровным, сдвигая каждую вершину к центру на случайное расстояние.
+        let shape = [(); Self::VERTEX_COUNT].map(|_| rng.gen_range(Self::MIN_VERTEX_DISTANCE, 1.0));
+
+        Self {
+            position,
+            prev_position: position,
+            speed,
+            radius,
+            shape,
+            rotation: rng.gen_range(0.0, std::f32::consts::TAU),
+            angular_velocity: rng
+                .gen_range(-config.max_angular_velocity, config.max_angular_velocity),
+            health: Self::max_health(radius),
+            near_miss: NearMiss::Far,
+            flash: 0.0,
+        }
+    }
+
+    /// Сколько попаданий выдерживает целый астероид радиусом `radius`.
+    fn max_health(radius: f32) -> u32 {
+        (radius / Self::HEALTH_RADIUS).ceil().max(1.0) as u32
+    }
+
+    /// Проверка выхода астероида далеко за границы экрана размером `width` на `height`.
+    /// Астероиды появляются выше экрана, поэтому верхняя граница не проверяется.
+    pub fn out_of_bounds(&self, width: f32, height: f32) -> bool {
+        let (x, y) = (self.position.x, self.position.y);
+        let left = -Self::BOUNDS_MARGIN;
+        let right = width + Self::BOUNDS_MARGIN;
+        let bottom = height + Self::BOUNDS_MARGIN;
+        x < left || x > right || y > bottom
+    }
+
+    /// Обновление состояния астероида.
+    pub fn update(&mut self, elapsed_time: f64, ship_speed: f32) {
+        let elapsed_time = elapsed_time as f32;
+        self.prev_position = self.position;
+        self.position += self.speed * elapsed_time;
+        self.position.y += ship_speed * elapsed_time;
+        self.rotation += self.angular_velocity * elapsed_time;
+        self.flash = (self.flash - elapsed_time).max(0.0);
+    }
+
+    /// Разгон астероида с ускорением `acceleration` в течение `elapsed_time`.
+    pub fn accelerate(&mut self, acceleration: Vec2, elapsed_time: f64) {
+        self.speed += acceleration * elapsed_time as f32;
+    }
+
+    /// Перенос астероида в окно изменившегося размера: положение меняется пропорционально
+    /// размерам окна, а размер и скорость астероида остаются прежними.
+    pub fn rescale(&mut self, scale: Vec2) {
+        self.position *= scale;
+        self.prev_position *= scale;
+    }
+
+    /// Попадание пули. Возвращает `true`, если астероид разрушен.
+    pub fn hit(&mut self) -> bool {
+        self.health = self.health.saturating_sub(1);
+        self.health == 0
+    }
+
+    /// Отслеживание пролёта рядом с кораблём. `close` - находится ли астероид в опасной
+    /// близости от корабля, `touched` - задевает ли он корабль.
+    ///
+    /// Возвращает `true`, когда астероид, побывав рядом с кораблём и ни разу его не задев,
+    /// удаляется от него. Для каждого астероида это происходит не больше одного раза,
+    /// и в этот момент астероид вспыхивает.
+    pub fn track_near_miss(&mut self, close: bool, touched: bool) -> bool {
+        match self.near_miss {
+            NearMiss::Done => false,
+            _ if touched => {
+                self.near_miss = NearMiss::Done;
+                false
+            }
+            _ if close => {
+                self.near_miss = NearMiss::Close;
+                false
+            }
+            NearMiss::Close => {
+                self.near_miss = NearMiss::Done;
+                self.flash = Self::FLASH_DURATION;
+                true
+            }
+            NearMiss::Far => false,
+        }
+    }
+
+    /// Отображение астероида. Повреждённый астероид темнее целого,
+    /// а пролетевший рядом с кораблём ненадолго вспыхивает золотым.
+    /// Цвета берутся из темы `theme`, а `alpha` - доля шага игрового мира, прошедшая
+    /// после последнего шага: астероид отображается на этой доле пути
+    /// от предыдущего положения к текущему.
+    pub fn draw(&self, theme: &Theme, alpha: f32) {
+        let max_health = Self::max_health(self.radius);
+        let brightness = if max_health > 1 {
+            let damage = (max_health - self.health) as f32 / (max_health - 1) as f32;
+            1.0 - (1.0 - Self::DAMAGED_BRIGHTNESS) * damage
+        } else {
+            1.0
+        };
+        let flash = self.flash / Self::FLASH_DURATION;
+        let mix = |channel: f32, flash_channel: f32| {
+            channel * brightness * (1.0 - flash) + flash_channel * flash
+        };
+        let base = theme.asteroid;
+        let color = Color::new(
+            mix(base.r, GOLD.r),
+            mix(base.g, GOLD.g),
+            mix(base.b, GOLD.b),
+            base.a,
+        );
+
+        let position = self.prev_position.lerp(self.position, alpha);
+        // Если есть текстура астероида, отображаем её, затемняя и подсвечивая так же.
+        // Когда объекты различаются по форме, нужны контуры, и текстура не используется.
+        if let Some(texture) = assets::textures().asteroid.filter(|_| !theme.shapes) {
+            let tint = Color::new(mix(1.0, GOLD.r), mix(1.0, GOLD.g), mix(1.0, GOLD.b), 1.0);
+            let size = Vec2::splat(2.0 * self.radius);
+            assets::draw_centered(texture, position, size, self.rotation, tint);
+            return;
+        }
+
+        // Отображаем астероид в виде веера треугольников с общей вершиной в центре.
+        let vertices = self.vertices(position);
+        for (i, &vertex) in vertices.iter().enumerate() {
+            let next = vertices[(i + 1) % vertices.len()];
+            draw_triangle(position, vertex, next, color);
+            draw_line(
+                vertex.x,
+                vertex.y,
+                next.x,
+                next.y,
+                2.0,
+                theme.asteroid_outline,
+            );
+        }
+
+        // Бронированный астероид, выдерживающий ещё несколько попаданий, помечаем
+        // кольцом на каждое попадание сверх последнего, чтобы не полагаться только на яркость.
+        if theme.shapes {
+            for ring in 1..self.health.min(Self::MAX_RINGS + 1) {
+                let radius = self.radius * 0.2 * ring as f32;
+                draw_circle_lines(position.x, position.y, radius, 2.0, theme.asteroid_outline);
+            }
+        }
+    }
+
+    /// Обводка астероида, который вот-вот врежется в корабль.
+    /// `alpha` - доля пути от предыдущего положения к текущему.
+    pub fn draw_threat_outline(&self, alpha: f32) {
+        /// Насколько обводка больше самого астероида.
+        const SCALE: f32 = 1.2;
+
+        let position = self.prev_position.lerp(self.position, alpha);
+        let vertices = self.vertices(position);
+        for (i, &vertex) in vertices.iter().enumerate() {
+            let next = vertices[(i + 1) % vertices.len()];
+            let (from, to) = (
+                position + (vertex - position) * SCALE,
+                position + (next - position) * SCALE,
+            );
+            draw_line(from.x, from.y, to.x, to.y, 2.0, RED);
+        }
+    }
+
+    /// Отображение предупреждения у верхнего края экрана там, где астероид вот-вот
+    /// появится. Пока астероид далеко или уже виден, предупреждения нет.
+    /// `ship_speed` - скорость корабля по вертикали, с которой астероид летит навстречу.
+    pub fn draw_entry_warning(&self, ship_speed: f32) {
+        // Наибольшая непрозрачность предупреждения: оно не должно отвлекать.
+        const MAX_ALPHA: f32 = 0.6;
+        const SIZE: f32 = 8.0;
+
+        let fall_speed = self.speed.y + ship_speed;
+        let distance = -(self.position.y + self.radius);
+        if distance <= 0.0 || fall_speed <= 0.0 {
+            return;
+        }
+        let time = distance / fall_speed;
+        if time > Self::WARNING_TIME {
+            return;
+        }
+
+        // Предупреждение проявляется по мере приближения астероида и указывает место,
+        // где он пересечёт верхний край с учётом смещения вбок.
+        let x = self.position.x + self.speed.x * time;
+        let alpha = MAX_ALPHA * (1.0 - time / Self::WARNING_TIME);
+        let color = Color::new(ORANGE.r, ORANGE.g, ORANGE.b, alpha);
+        draw_triangle(
+            Vec2::new(x - SIZE, 0.0),
+            Vec2::new(x + SIZE, 0.0),
+            Vec2::new(x, SIZE * 1.5),
+            color,
+        );
+    }
+
+    /// Вершины многоугольника астероида с центром в `position` с учётом его поворота.
+    fn vertices(&self, position: Vec2) -> [Vec2; Self::VERTEX_COUNT] {
+        let step = std::f32::consts::TAU / Self::VERTEX_COUNT as f32;
+        let mut vertices = [Vec2::ZERO; Self::VERTEX_COUNT];
+        for (i, (vertex, &distance)) in vertices.iter_mut().zip(&self.shape).enumerate() {
+            let angle = self.rotation + step * i as f32;
+            *vertex = position + Vec2::new(angle.cos(), angle.sin()) * self.radius * distance;
+        }
+        vertices
+    }
+
+    /// Раскалывание астероида на два или три осколка, разлетающихся в разные стороны
+    /// и вращающихся в разные стороны. Осколки сохраняют форму исходного астероида.
+    ///
+    /// Число осколков и скорости их разлёта берутся из генератора `rng`, поэтому
+    /// при одинаковом зерне астероиды раскалываются одинаково. Площадь астероида
+    /// делится между осколками поровну. Масса осколков пропорциональна площади,
+    /// и импульс сохраняется: каждый осколок летит со скоростью астероида плюс
+    /// случайная скорость разлёта, а сумма скоростей разлёта равна нулю.
+    /// Если осколки получились бы меньше `config.min_fragment_radius`, то их меньше,
+    /// а если меньше и двух осколков, то их нет. Память для осколков не выделяется.
+    pub fn fragment(&self, rng: &mut Rng, config: &GameConfig) -> impl Iterator<Item = Asteroid> {
+        const MIN_COUNT: usize = 2;
+        const MAX_COUNT: usize = 3;
+        const PUSH: f32 = 50.0; // Средняя скорость, с которой осколки расталкиваются в стороны.
+        const ANGLE_JITTER: f32 = 0.5; // Наибольшее отклонение направления разлёта в радианах.
+
+        let mut count = if rng.gen_range(0.0, 1.0) < 0.5 {
+            MIN_COUNT
+        } else {
+            MAX_COUNT
+        };
+        let radius = |count: usize| self.radius / (count as f32).sqrt();
+        while count > MIN_COUNT && radius(count) < config.min_fragment_radius {
+            count -= 1;
+        }
+        let radius = radius(count);
+        if radius < config.min_fragment_radius {
+            count = 0;
+        }
+
+        // Направления разлёта распределены по кругу и случайно сбиты, а скорости случайны.
+        // Среднюю скорость разлёта вычитаем, чтобы сохранить импульс.
+        let offset = rng.gen_range(0.0, std::f32::consts::TAU);
+        let mut spread = [Vec2::ZERO; MAX_COUNT];
+        for (i, spread) in spread[..count].iter_mut().enumerate() {
+            let angle = offset
+                + std::f32::consts::TAU * i as f32 / count as f32
+                + rng.gen_range(-ANGLE_JITTER, ANGLE_JITTER);
+            let speed = rng.gen_range(PUSH / 2.0, PUSH * 1.5);
+            *spread = Vec2::new(angle.cos(), angle.sin()) * speed;
+        }
+        let mean = spread[..count].iter().sum::<Vec2>() / count.max(1) as f32;
+
+        let (position, speed, shape, rotation) =
+            (self.position, self.speed, self.shape, self.rotation);
+        let angular_velocity = self.angular_velocity.abs();
+        spread
+            .into_iter()
+            .take(count)
+            .enumerate()
+            .map(move |(i, spread)| {
+                let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+                Asteroid {
+                    position,
+                    prev_position: position,
+                    speed: speed + spread - mean,
+                    radius,
+                    shape,
+                    rotation,
+                    angular_velocity: side * angular_velocity,
+                    health: Self::max_health(radius),
+                    near_miss: NearMiss::Far,
+                    flash: 0.0,
+                }
+            })
+    }
+
+    /// Упругое столкновение с астероидом `other`, если они пересекаются.
+    ///
+    /// Массы астероидов считаются пропорциональными их радиусам. Астероиды обмениваются
+    /// составляющими скоростей вдоль линии, соединяющей их центры, и расталкиваются,
+    /// чтобы не слипнуться. Расталкивание ограничено по скорости, поэтому астероиды,
+    /// появившиеся друг в друге (например, осколки одного астероида), расходятся плавно,
+    /// без рывков за время кадра `elapsed_time`.
+    #[cfg(feature = "asteroid-collisions")]
+    pub fn collide(&mut self, other: &mut Asteroid, elapsed_time: f32) {
+        const MAX_SEPARATION_SPEED: f32 = 100.0;
+
+        let delta = other.position - self.position;
+        let distance = delta.length();
+        let overlap = self.radius + other.radius - distance;
+        // Центры совпадают только у только что появившихся осколков:
+        // направление столкновения не определено, а разлетаются они и так.
+        if overlap <= 0.0 || distance <= f32::EPSILON {
+            return;
+        }
+        let normal = delta / distance;
+        let total_mass = self.radius + other.radius;
+
+        // Обмениваемся скоростями, только если астероиды сближаются.
+        // Иначе уже расходящиеся астероиды дрожали бы, сталкиваясь каждый кадр.
+        let approach = (other.speed - self.speed).dot(normal);
+        if approach < 0.0 {
+            self.speed += normal * (2.0 * other.radius / total_mass * approach);
+            other.speed -= normal * (2.0 * self.radius / total_mass * approach);
+        }
+
+        // Расталкиваем астероиды: лёгкий сдвигается сильнее тяжёлого.
+        let push = overlap.min(MAX_SEPARATION_SPEED * elapsed_time);
+        self.position -= normal * (push * other.radius / total_mass);
+        other.position += normal * (push * self.radius / total_mass);
+    }
+
+    /// Положение центра астероида.
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Скорость астероида.
+    pub fn speed(&self) -> Vec2 {
+        self.speed
+    }
+
+    /// Радиус астероида.
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Находится ли точка `point` внутри астероида.
+    pub fn contains(&self, point: Vec2) -> bool {
+        (point - self.position).length() < self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: f32 = 800.0;
+    const HEIGHT: f32 = 600.0;
+    const MARGIN: f32 = Asteroid::BOUNDS_MARGIN;
+
+    /// Неподвижный астероид с центром в точке (`x`, `y`).
+    fn asteroid_at(x: f32, y: f32) -> Asteroid {
+        Asteroid {
+            position: Vec2::new(x, y),
+            prev_position: Vec2::new(x, y),
+            speed: Vec2::ZERO,
+            radius: 50.0,
+            shape: [1.0; Asteroid::VERTEX_COUNT],
+            rotation: 0.0,
+            angular_velocity: 0.0,
+            health: 1,
+            near_miss: NearMiss::Far,
+            flash: 0.0,
+        }
+    }
+
+    fn out_of_bounds(x: f32, y: f32) -> bool {
+        asteroid_at(x, y).out_of_bounds(WIDTH, HEIGHT)
+    }
+
+    #[test]
+    fn on_screen_is_in_bounds() {
+        assert!(!out_of_bounds(WIDTH / 2.0, HEIGHT / 2.0));
+        assert!(!out_of_bounds(0.0, 0.0));
+        assert!(!out_of_bounds(WIDTH, HEIGHT));
+    }
+
+    #[test]
+    fn just_inside_margin_is_in_bounds() {
+        assert!(!out_of_bounds(-MARGIN + 1.0, HEIGHT / 2.0));
+        assert!(!out_of_bounds(WIDTH + MARGIN - 1.0, HEIGHT / 2.0));
+        assert!(!out_of_bounds(WIDTH / 2.0, HEIGHT + MARGIN - 1.0));
+    }
+
+    #[test]
+    fn just_outside_margin_is_out_of_bounds() {
+        assert!(out_of_bounds(-MARGIN - 1.0, HEIGHT / 2.0));
+        assert!(out_of_bounds(WIDTH + MARGIN + 1.0, HEIGHT / 2.0));
+        assert!(out_of_bounds(WIDTH / 2.0, HEIGHT + MARGIN + 1.0));
+    }
+
+    #[test]
+    fn far_outside_is_out_of_bounds() {
+        assert!(out_of_bounds(-10_000.0, HEIGHT / 2.0));
+        assert!(out_of_bounds(WIDTH + 10_000.0, HEIGHT / 2.0));
+        assert!(out_of_bounds(WIDTH / 2.0, HEIGHT + 10_000.0));
+    }
+
+    #[test]
+    fn above_top_is_in_bounds() {
+        // Астероиды появляются над экраном и не должны сразу забываться.
+        assert!(!out_of_bounds(WIDTH / 2.0, -MARGIN - 1.0));
+        assert!(!out_of_bounds(WIDTH / 2.0, -10_000.0));
+    }
+
+    #[test]
+    fn fragment_conserves_area_and_momentum() {
+        let config = GameConfig::default();
+        let mut rng = Rng::new(42);
+        let mut asteroid = asteroid_at(WIDTH / 2.0, HEIGHT / 2.0);
+        asteroid.speed = Vec2::new(30.0, 120.0);
+        for _ in 0..20 {
+            let fragments: Vec<_> = asteroid.fragment(&mut rng, &config).collect();
+            assert!((2..=3).contains(&fragments.len()));
+
+            // Суммарная площадь осколков равна площади астероида.
+            let area: f32 = fragments
+                .iter()
+                .map(|fragment| fragment.radius.powi(2))
+                .sum();
+            assert!((area - asteroid.radius.powi(2)).abs() < 1e-3);
+
+            // Осколки равной массы, поэтому импульс сохраняется, если средняя скорость
+            // осколков равна скорости астероида. Сами осколки летят с разными скоростями.
+            let speed = fragments
+                .iter()
+                .map(|fragment| &fragment.speed)
+                .sum::<Vec2>()
+                / fragments.len() as f32;
+            assert!((speed - asteroid.speed).length() < 1e-3);
+            assert!(fragments
+                .iter()
+                .all(|fragment| (fragment.speed - asteroid.speed).length() > 1.0));
+        }
+    }
+
+    #[test]
+    fn fragment_count_is_random_and_repeatable() {
+        let config = GameConfig::default();
+        let asteroid = asteroid_at(WIDTH / 2.0, HEIGHT / 2.0);
+        let counts = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..20)
+                .map(|_| asteroid.fragment(&mut rng, &config).count())
+                .collect::<Vec<_>>()
+        };
+        // При одинаковом зерне астероид раскалывается одинаково,
+        // а число осколков бывает и два, и три.
+        assert_eq!(counts(7), counts(7));
+        assert!(counts(7).contains(&2));
+        assert!(counts(7).contains(&3));
+    }
+
+    #[test]
+    fn fragments_are_never_below_minimum() {
+        let config = GameConfig::default();
+        let mut rng = Rng::new(42);
+        let mut asteroid = asteroid_at(WIDTH / 2.0, HEIGHT / 2.0);
+        // Раскалываем первые осколки, пока они не перестанут раскалываться.
+        for _ in 0..10 {
+            match asteroid.fragment(&mut rng, &config).next() {
+                Some(fragment) => {
+                    assert!(fragment.radius >= config.min_fragment_radius);
+                    asteroid = fragment;
+                }
+                None => break,
+            }
+        }
+        assert!(asteroid.radius < config.min_fragment_radius * std::f32::consts::SQRT_2);
+    }
+}
@@ -0,0 +1,57 @@
+//! Автопилот, играющий за игрока в демонстрации на фоне меню.
+
+use crate::asteroid::Asteroid;
+use crate::input::Input;
+use crate::pool::Pool;
+use crate::ship::Ship;
+
+/// Насколько далеко вперёд (в секундах) автопилот предсказывает движение астероидов.
+const LOOKAHEAD: f32 = 1.5;
+/// Запас по горизонтали между астероидом и центром корабля, включающий половину его ширины.
+const CLEARANCE: f32 = 40.0;
+/// Расстояние от края экрана шириной `width`, ближе которого корабль не уходит от астероида.
+const EDGE: f32 = 60.0;
+
+/// Управление кораблём `ship` на экране шириной `width`.
+///
+/// Автопилот находит астероид, который раньше других окажется над кораблём, уводит корабль
+/// в сторону от его центра и стреляет в него. Упёршись в край экрана, корабль уходит
+/// в другую сторону.
+pub fn input(ship: &Ship, asteroids: &Pool<Asteroid>, width: f32) -> Input {
+    let center = ship.center();
+    let threat = asteroids
+        .iter()
+        .filter_map(|(_, asteroid)| {
+            // Астероиды движутся вниз относительно корабля ещё и со скоростью его полёта.
+            let fall_speed = asteroid.speed().y + ship.smoothed_vertical_speed();
+            let height = center.y - asteroid.position().y;
+            if fall_speed <= 0.0 || height < -asteroid.radius() {
+                return None; // Астероид не догонит корабль или уже пролетел мимо.
+            }
+            let time = height.max(0.0) / fall_speed;
+            let x = asteroid.position().x + asteroid.speed().x * time;
+            let close = (x - center.x).abs() < asteroid.radius() + CLEARANCE;
+            (time < LOOKAHEAD && close).then_some((time, x))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+
+    let steer = match threat {
+        Some((_, x)) => {
+            let away = if center.x < x { -1.0 } else { 1.0 };
+            let blocked =
+                (away < 0.0 && center.x < EDGE) || (away > 0.0 && center.x > width - EDGE);
+            if blocked {
+                -away
+            } else {
+                away
+            }
+        }
+        None => 0.0,
+    };
+
+    Input {
+        steer,
+        fire: threat.is_some(),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,61 @@
+//! Пули, выпускаемые кораблём.
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Состояние пули.
+#[derive(Serialize, Deserialize)]
+pub struct Bullet {
+    position: Vec2,
+    /// Положение перед последним шагом игрового мира, для плавного отображения между шагами.
+    prev_position: Vec2,
+    speed: Vec2,
+}
+
+impl Bullet {
+    // Параметры пуль.
+    const SPEED: f32 = 600.0;
+    const RADIUS: f32 = 3.0;
+
+    /// Создание пули, летящей из точки `position` в направлении `direction`.
+    pub fn new(position: Vec2, direction: Vec2) -> Self {
+        Self {
+            position,
+            prev_position: position,
+            speed: direction * Self::SPEED,
+        }
+    }
+
+    /// Проверка выхода пули за границы экрана размером `width` на `height`.
+    pub fn out_of_bounds(&self, width: f32, height: f32) -> bool {
+        let (x, y) = (self.position.x, self.position.y);
+        x < -Self::RADIUS
+            || x > width + Self::RADIUS
+            || y < -Self::RADIUS
+            || y > height + Self::RADIUS
+    }
+
+    /// Обновление состояния пули.
+    pub fn update(&mut self, elapsed_time: f64) {
+        self.prev_position = self.position;
+        self.position += self.speed * elapsed_time as f32;
+    }
+
+    /// Масштабирование положения при изменении размеров окна
+    /// (`scale` - отношение новых размеров к старым).
+    pub fn rescale(&mut self, scale: Vec2) {
+        self.position *= scale;
+        self.prev_position *= scale;
+    }
+
+    /// Положение пули.
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Отображение пули на доле `alpha` пути от предыдущего положения к текущему.
+    pub fn draw(&self, alpha: f32) {
+        let position = self.prev_position.lerp(self.position, alpha);
+        draw_circle(position.x, position.y, Self::RADIUS, YELLOW);
+    }
+}
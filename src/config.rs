@@ -0,0 +1,234 @@
+//! Настройки игровой механики, загружаемые из файла при запуске.
+
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Файл с настройками игровой механики.
+#[cfg(not(target_arch = "wasm32"))]
+const CONFIG_FILE: &str = "config.toml";
+
+/// Параметры движения корабля и астероидов.
+/// Позволяют подобрать ощущение от управления без перекомпиляции.
+///
+/// В файле можно указать только часть параметров: остальные берутся по умолчанию.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    /// Ускорение корабля в классическом режиме в пикселях в секунду за секунду.
+    pub ship_acceleration: f32,
+    /// Доля скорости, теряемая кораблём за секунду в классическом режиме.
+    /// Предельная скорость корабля равна `ship_acceleration / ship_deceleration`.
+    pub ship_deceleration: f32,
+    /// Ускорение полёта корабля по вертикали в пикселях в секунду за секунду.
+    pub vertical_acceleration: f32,
+    /// Скорость поворота корабля в свободном полёте в радианах в секунду.
+    pub rotation_speed: f32,
+    /// Ускорение от тяги в свободном полёте в пикселях в секунду за секунду.
+    pub thrust: f32,
+    /// Доля скорости, теряемая кораблём за секунду в свободном полёте.
+    pub drag: f32,
+    /// Наибольшая скорость вращения астероида в радианах в секунду.
+    pub max_angular_velocity: f32,
+    /// Наименьший радиус осколка астероида.
+    pub min_fragment_radius: f32,
+    /// Наибольшее число астероидов одновременно. Осколки сверх него не появляются.
+    pub max_asteroids: usize,
+    /// Сколько очков стоит продолжение игры после гибели.
+    pub continue_cost: u64,
+    /// Время в секундах, за которое разница между текущей и наибольшей сложностью
+    /// уменьшается в e раз. Чем меньше, тем быстрее игра становится сложнее.
+    pub progression_time: f32,
+    /// На какую долю растёт наибольшая скорость астероидов к наибольшей сложности.
+    pub speed_growth: f32,
+    /// Насколько чаще появляются крупные астероиды к наибольшей сложности.
+    pub size_growth: f32,
+    /// Ширина окна при запуске в пикселях.
+    pub window_width: i32,
+    /// Высота окна при запуске в пикселях.
+    pub window_height: i32,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            ship_acceleration: 2000.0,
+            ship_deceleration: 5.0,
+            vertical_acceleration: 50.0,
+            rotation_speed: 4.0,
+            thrust: 400.0,
+            drag: 0.5,
+            max_angular_velocity: 2.0,
+            min_fragment_radius: 25.0,
+            max_asteroids: 150,
+            continue_cost: 1000,
+            progression_time: 60.0,
+            speed_growth: 0.5,
+            size_growth: 1.0,
+            window_width: 800,
+            window_height: 600,
+        }
+    }
+}
+
+impl GameConfig {
+    /// Загрузка настроек из файла `config.toml`.
+    ///
+    /// Если файла нет, используются настройки по умолчанию. Если файл не удалось
+    /// разобрать, то тоже, а параметры с недопустимыми значениями остаются по умолчанию.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Self {
+        match std::fs::read_to_string(CONFIG_FILE) {
+            Ok(text) => Self::parse(&text),
+            Err(_) => Self::default(), // Файла нет - используем настройки по умолчанию.
+        }
+    }
+
+    /// В браузере файловой системы нет, поэтому используются настройки по умолчанию.
+    #[cfg(target_arch = "wasm32")]
+    pub fn load() -> Self {
+        Self::default()
+    }
+
+    /// Разбор настроек из текста `text` в формате TOML, например `ship_acceleration = 1500.0`.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn parse(text: &str) -> Self {
+        let mut config = match toml::from_str::<Self>(text) {
+            Ok(config) => config,
+            Err(err) => {
+                warn!("Failed to parse game config: {}", err);
+                return Self::default();
+            }
+        };
+        config.validate();
+        config
+    }
+
+    /// Замена недопустимых значений параметров значениями по умолчанию.
+    ///
+    /// Скорости, ускорения и размеры, на которые делится или без которых игра
+    /// не работает, должны быть положительными, а остальные дробные параметры -
+    /// неотрицательными: например, `vertical_acceleration = 0` отключает разгон.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn validate(&mut self) {
+        let default = Self::default();
+        let positive = |value: f32| value.is_finite() && value > 0.0;
+        let non_negative = |value: f32| value.is_finite() && value >= 0.0;
+
+        check(
+            "ship_acceleration",
+            &mut self.ship_acceleration,
+            default.ship_acceleration,
+            positive,
+        );
+        check(
+            "ship_deceleration",
+            &mut self.ship_deceleration,
+            default.ship_deceleration,
+            positive,
+        );
+        check(
+            "vertical_acceleration",
+            &mut self.vertical_acceleration,
+            default.vertical_acceleration,
+            non_negative,
+        );
+        check(
+            "rotation_speed",
+            &mut self.rotation_speed,
+            default.rotation_speed,
+            positive,
+        );
+        check("thrust", &mut self.thrust, default.thrust, positive);
+        check("drag", &mut self.drag, default.drag, non_negative);
+        check(
+            "max_angular_velocity",
+            &mut self.max_angular_velocity,
+            default.max_angular_velocity,
+            non_negative,
+        );
+        check(
+            "min_fragment_radius",
+            &mut self.min_fragment_radius,
+            default.min_fragment_radius,
+            positive,
+        );
+        check(
+            "progression_time",
+            &mut self.progression_time,
+            default.progression_time,
+            positive,
+        );
+        check(
+            "speed_growth",
+            &mut self.speed_growth,
+            default.speed_growth,
+            non_negative,
+        );
+        check(
+            "size_growth",
+            &mut self.size_growth,
+            default.size_growth,
+            non_negative,
+        );
+        check(
+            "window_width",
+            &mut self.window_width,
+            default.window_width,
+            |value| value > 0,
+        );
+        check(
+            "window_height",
+            &mut self.window_height,
+            default.window_height,
+            |value| value > 0,
+        );
+    }
+}
+
+/// Замена значения `value` параметра `name` значением по умолчанию `default`,
+/// если `valid` считает его недопустимым.
+#[cfg(not(target_arch = "wasm32"))]
+fn check<T: Copy + std::fmt::Display>(
+    name: &str,
+    value: &mut T,
+    default: T,
+    valid: impl Fn(T) -> bool,
+) {
+    if !valid(*value) {
+        warn!(
+            "Invalid game config value {} = {}, using {}",
+            name, value, default
+        );
+        *value = default;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keeps_defaults_and_rejects_invalid_values() {
+        let config = GameConfig::parse(
+            "vertical_acceleration = 0\n\
+             thrust = -5.0\n\
+             max_asteroids = 20\n\
+             window_width = 1024\n",
+        );
+        let default = GameConfig::default();
+        // Нулевой разгон допустим, а отрицательная тяга - нет.
+        assert_eq!(config.vertical_acceleration, 0.0);
+        assert_eq!(config.thrust, default.thrust);
+        assert_eq!(config.max_asteroids, 20);
+        assert_eq!(config.window_width, 1024);
+        // Не упомянутые параметры остаются по умолчанию.
+        assert_eq!(config.window_height, default.window_height);
+        assert_eq!(config.continue_cost, default.continue_cost);
+    }
+
+    #[test]
+    fn parse_error_falls_back_to_defaults() {
+        let config = GameConfig::parse("max_asteroids = 1.5\n");
+        assert_eq!(config.max_asteroids, GameConfig::default().max_asteroids);
+    }
+}
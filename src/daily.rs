@@ -0,0 +1,135 @@
+//! Ежедневное испытание: одна и та же игра для всех в течение суток.
+//!
+//! Зерно генератора случайных чисел берётся из текущей даты (по UTC), поэтому
+//! в этот день все игроки встречают одинаковую последовательность астероидов.
+
+use crate::storage;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Файл, в котором хранится лучшее время испытания.
+const DAILY_FILE: &str = "daily.json";
+
+/// Сегодняшняя дата по UTC в виде числа `ГГГГММДД`, например `20240131`.
+pub fn today() -> u32 {
+    const SECONDS_PER_DAY: f64 = 86_400.0;
+    let days = (miniquad::date::now() / SECONDS_PER_DAY).floor() as i64;
+    date_from_days(days)
+}
+
+/// Запись даты `date` из числа `ГГГГММДД` строкой `ГГГГ-ММ-ДД`.
+pub fn format_date(date: u32) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date / 10_000,
+        date / 100 % 100,
+        date % 100
+    )
+}
+
+/// Зерно генератора случайных чисел для испытания в день `date`.
+pub fn seed(date: u32) -> u64 {
+    date as u64
+}
+
+/// Дата в виде числа `ГГГГММДД` через `days` дней после 1 января 1970 года.
+///
+/// Перевод из числа дней в григорианский календарь по алгоритму Говарда Хиннанта:
+/// годы отсчитываются с 1 марта, чтобы високосный день оказался в конце года.
+fn date_from_days(days: i64) -> u32 {
+    let days = days + 719_468; // Дни с 1 марта 0 года.
+    let era = days.div_euclid(146_097); // Полные 400-летние циклы.
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153; // Месяцы с марта.
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year * 10_000 + month * 100 + day) as u32
+}
+
+/// Лучшее время ежедневного испытания. Хранится только за один день:
+/// с наступлением нового дня прежний результат забывается.
+#[derive(Default, Serialize, Deserialize)]
+pub struct DailyBest {
+    /// День, к которому относится результат, в виде числа `ГГГГММДД`.
+    date: u32,
+    /// Лучшее время в этот день.
+    time: f64,
+}
+
+impl DailyBest {
+    /// Лучшее время в день `date` или ноль, если в этот день испытание не проходилось.
+    pub fn best(&self, date: u32) -> f64 {
+        if self.date == date {
+            self.time
+        } else {
+            0.0
+        }
+    }
+
+    /// Учёт результата `time` испытания дня `date`. Возвращает, стал ли он лучшим за день.
+    pub fn record(&mut self, date: u32, time: f64) -> bool {
+        if time <= self.best(date) {
+            return false;
+        }
+        *self = Self { date, time };
+        true
+    }
+
+    /// Загрузка результата из файла. Если файла нет или он повреждён, то результата нет.
+    pub fn load() -> Self {
+        let text = match storage::read(DAILY_FILE) {
+            Some(text) => text,
+            None => return Self::default(),
+        };
+        serde_json::from_str(&text).unwrap_or_else(|err| {
+            warn!("Failed to parse daily challenge best: {}", err);
+            Self::default()
+        })
+    }
+
+    /// Сохранение результата в файл. Ошибка записи не прерывает игру.
+    pub fn save(&self) {
+        let result = serde_json::to_string_pretty(self)
+            .map_err(|err| err.to_string())
+            .and_then(|text| storage::write(DAILY_FILE, &text).map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            warn!("Failed to save daily challenge best: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(date_from_days(0), 19700101);
+        assert_eq!(date_from_days(59), 19700301);
+        assert_eq!(date_from_days(11_016), 20000229);
+        assert_eq!(date_from_days(19_722), 20231231);
+        assert_eq!(date_from_days(19_723), 20240101);
+        assert_eq!(date_from_days(-1), 19691231);
+        assert_eq!(format_date(20240131), "2024-01-31");
+    }
+
+    #[test]
+    fn new_day_resets_best() {
+        let mut daily = DailyBest::default();
+        assert!(daily.record(20240101, 10.0));
+        assert!(!daily.record(20240101, 5.0));
+        assert_eq!(daily.best(20240101), 10.0);
+        // На следующий день прежний результат не считается.
+        assert_eq!(daily.best(20240102), 0.0);
+        assert!(daily.record(20240102, 3.0));
+        assert_eq!(daily.best(20240101), 0.0);
+    }
+}
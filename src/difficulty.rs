@@ -0,0 +1,202 @@
+//! Уровни сложности.
+
+use crate::config::GameConfig;
+use crate::lang::TextKey;
+use crate::rng::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Уровень сложности игры.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// Астероиды появляются реже, они меньше и летят медленнее.
+    Easy,
+    /// Обычная игра.
+    Normal,
+    /// Астероиды появляются чаще, они крупнее и летят быстрее.
+    Hard,
+    /// Астероиды появляются ещё чаще и летят ещё быстрее, а корабль тяжелее в управлении.
+    Insane,
+}
+
+impl Difficulty {
+    /// Все уровни от лёгкого к безумному.
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Insane,
+    ];
+
+    /// Индекс уровня в [`Difficulty::ALL`].
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Следующий, более сложный уровень (для переключения в настройках).
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard | Difficulty::Insane => Difficulty::Insane,
+        }
+    }
+
+    /// Предыдущий, более лёгкий уровень (для переключения в настройках).
+    pub fn previous(self) -> Self {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => Difficulty::Easy,
+            Difficulty::Hard => Difficulty::Normal,
+            Difficulty::Insane => Difficulty::Hard,
+        }
+    }
+
+    /// Название уровня для отображения.
+    pub fn name(self) -> TextKey {
+        match self {
+            Difficulty::Easy => TextKey::DifficultyEasy,
+            Difficulty::Normal => TextKey::DifficultyNormal,
+            Difficulty::Hard => TextKey::DifficultyHard,
+            Difficulty::Insane => TextKey::DifficultyInsane,
+        }
+    }
+
+    /// Название уровня в файлах настроек и записей.
+    pub fn key(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Insane => "insane",
+        }
+    }
+
+    /// Уровень по его названию в файле, см. [`Difficulty::key`].
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            "insane" => Some(Difficulty::Insane),
+            _ => None,
+        }
+    }
+
+    /// Множитель интервала появления астероидов.
+    pub fn spawn_interval_scale(self) -> f64 {
+        match self {
+            Difficulty::Easy => 1.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.7,
+            Difficulty::Insane => 0.5,
+        }
+    }
+
+    /// Наибольшее число астероидов, появляющихся одновременно. Больше трёх за раз
+    /// не бывает, чтобы астероиды не выстраивались в стену, через которую не пролететь.
+    pub fn max_spawn_count(self) -> u32 {
+        match self {
+            Difficulty::Easy => 1,
+            Difficulty::Normal => 2,
+            Difficulty::Hard | Difficulty::Insane => 3,
+        }
+    }
+
+    /// Притягивают ли гравитационные колодцы не только астероиды, но и корабль.
+    pub fn gravity_pulls_ship(self) -> bool {
+        matches!(self, Difficulty::Hard | Difficulty::Insane)
+    }
+
+    /// Множитель ускорений корабля: на лёгком уровне корабль отзывчивее,
+    /// а на безумном - тяжелее.
+    pub fn ship_acceleration_scale(self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.2,
+            Difficulty::Normal | Difficulty::Hard => 1.0,
+            Difficulty::Insane => 0.8,
+        }
+    }
+
+    /// Настройки `config` с ускорениями корабля, изменёнными под уровень сложности.
+    pub fn ship_config(self, config: GameConfig) -> GameConfig {
+        let scale = self.ship_acceleration_scale();
+        GameConfig {
+            ship_acceleration: config.ship_acceleration * scale,
+            thrust: config.thrust * scale,
+            ..config
+        }
+    }
+
+    /// Наибольшая составляющая скорости астероида по каждой из осей.
+    pub fn max_speed(self) -> f32 {
+        match self {
+            Difficulty::Easy => 140.0,
+            Difficulty::Normal => 200.0,
+            Difficulty::Hard => 260.0,
+            Difficulty::Insane => 320.0,
+        }
+    }
+
+    /// Наименьший радиус нового астероида.
+    pub fn min_radius(self) -> f32 {
+        match self {
+            Difficulty::Easy => 20.0,
+            Difficulty::Normal => 25.0,
+            Difficulty::Hard | Difficulty::Insane => 30.0,
+        }
+    }
+
+    /// Наибольший радиус нового астероида.
+    pub fn max_radius(self) -> f32 {
+        match self {
+            Difficulty::Easy => 70.0,
+            Difficulty::Normal => 100.0,
+            Difficulty::Hard | Difficulty::Insane => 120.0,
+        }
+    }
+}
+
+/// Нарастание сложности по ходу игры.
+///
+/// Уровень нарастания равен 0 в начале игры и плавно приближается к 1. С ним сокращается
+/// интервал появления астероидов, растёт их наибольшая скорость и чаще появляются
+/// крупные астероиды. Насколько именно - задают параметры в [`GameConfig`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Progression {
+    level: f32,
+}
+
+impl Progression {
+    /// Начало игры: сложность ещё не выросла.
+    pub const START: Progression = Progression { level: 0.0 };
+
+    /// Нарастание, при котором разница с наибольшей сложностью составляет долю `remaining`.
+    pub fn from_remaining(remaining: f64) -> Self {
+        Self {
+            level: (1.0 - remaining).clamp(0.0, 1.0) as f32,
+        }
+    }
+
+    /// Нарастание через `time` секунд игры. Разница с наибольшей сложностью
+    /// уменьшается в e раз за `config.progression_time` секунд.
+    pub fn at_time(time: f64, config: &GameConfig) -> Self {
+        Self::from_remaining((-time / config.progression_time as f64).exp())
+    }
+
+    /// Уровень нарастания от 0 до 1.
+    pub fn level(self) -> f32 {
+        self.level
+    }
+
+    /// Наибольшая составляющая скорости нового астероида по каждой из осей.
+    pub fn max_speed(self, difficulty: Difficulty, config: &GameConfig) -> f32 {
+        difficulty.max_speed() * (1.0 + config.speed_growth * self.level)
+    }
+
+    /// Случайный радиус нового астероида. Вначале все радиусы от наименьшего
+    /// до наибольшего равновероятны, а с ростом сложности крупные выпадают чаще.
+    pub fn radius(self, rng: &mut Rng, difficulty: Difficulty, config: &GameConfig) -> f32 {
+        let bias = 1.0 / (1.0 + config.size_growth * self.level);
+        let share = rng.gen_range(0.0, 1.0).powf(bias);
+        difficulty.min_radius() + (difficulty.max_radius() - difficulty.min_radius()) * share
+    }
+}
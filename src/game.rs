@@ -0,0 +1,2173 @@
+//! Игровой процесс.
+
+use crate::assets;
+use crate::asteroid::Asteroid;
+use crate::autopilot;
+use crate::bullet::Bullet;
+use crate::config::GameConfig;
+use crate::difficulty::{Difficulty, Progression};
+use crate::ghost::Ghost;
+use crate::gravity::GravityWell;
+use crate::input::{AxisResponse, Gamepad, Input, KeyBindings};
+use crate::input_display::InputDisplay;
+use crate::lang::{Lang, TextKey};
+use crate::overlay::{DebugOverlay, ObjectCounts, QualityInfo, WorldBounds};
+use crate::particle::{Particle, ParticleSystem};
+use crate::pool::Pool;
+use crate::popup::Popup;
+use crate::powerup::{PowerUp, PowerUpKind};
+use crate::quality::Quality;
+use crate::radar::Radar;
+use crate::replay::{FrameInput, Replay};
+use crate::rng::Rng;
+use crate::seeker::Seeker;
+use crate::ship::{FlightMode, Ship};
+use crate::sound::Sounds;
+use crate::spatial::SpatialGrid;
+use crate::starfield::Starfield;
+use crate::theme::Theme;
+use crate::threat;
+use crate::ui::{draw_centered_text, draw_ui_text, measure_ui_text};
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Файл, в который отладочная сборка сохраняет состояние игры по клавише F5.
+#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+const STATE_FILE: &str = "state.json";
+
+/// Итоги завершённой игры.
+#[derive(Clone, Copy)]
+pub struct RunStats {
+    /// Время, которое игроку удалось продержаться.
+    pub time: f64,
+    /// Число астероидов, пролетевших мимо корабля.
+    pub dodged: u32,
+    /// Число астероидов, уничтоженных пулями и бомбами.
+    pub destroyed: u32,
+    /// Число пройденных волн, если астероиды появлялись волнами.
+    pub waves_cleared: Option<u32>,
+    /// Очки, набранные за пройденный путь, уничтожение астероидов и пролёты рядом с ними.
+    pub score: u64,
+    /// Время, которое нужно было продержаться, если это режим на время.
+    pub goal: Option<f64>,
+    /// Достигнута ли цель режима на время.
+    pub won: bool,
+    /// Оставшиеся жизни.
+    pub lives: u32,
+    /// Уровень сложности игры.
+    pub difficulty: Difficulty,
+    /// День ежедневного испытания в виде числа `ГГГГММДД`, если это оно.
+    pub daily: Option<u32>,
+    /// Зерно генератора случайных чисел, с которым игру можно повторить.
+    pub seed: u64,
+}
+
+/// Состояние игрового процесса.
+///
+/// Состояние можно сохранить в файл и восстановить (см. [`Game::save_state`]).
+/// Не сохраняется то, что относится к приложению, а не к игре: звуки, клавиши,
+/// отладочный оверлей и радар. При восстановлении они остаются от текущей игры.
+#[derive(Serialize, Deserialize)]
+pub struct Game {
+    /// Время в текущей игре: сумма длительностей всех кадров после обратного отсчёта,
+    /// кроме кадров на паузе.
+    time: f64,
+    /// Оставшееся время обратного отсчёта перед началом игры.
+    countdown: f64,
+    /// Время начала предыдущего обновления состояния игры.
+    /// Это время с запуска приложения, поэтому при восстановлении оно отсчитывается заново.
+    #[serde(skip)]
+    last_update: f64,
+    /// Корабль игрока.
+    ship: Ship,
+    /// Таймер появления астероидов.
+    asteroid_timer: f64,
+    /// Появляются ли астероиды волнами вместо непрерывного потока.
+    waves: bool,
+    /// Номер текущей волны, начиная с единицы.
+    wave: u32,
+    /// Сколько астероидов текущей волны ещё не появилось.
+    wave_budget: u32,
+    /// Оставшееся время перерыва после пройденной волны.
+    wave_break: f64,
+    /// Вектор астероидов.
+    asteroids: Pool<Asteroid>,
+    /// Число астероидов, пролетевших мимо корабля.
+    dodged: u32,
+    /// Число астероидов, уничтоженных пулями и бомбами.
+    destroyed: u32,
+    /// Очки, набранные за пройденный путь, уничтожение астероидов и пролёты рядом с ними.
+    score: u64,
+    /// Доля очка за пройденный путь, ещё не добавленная к очкам.
+    distance_score: f64,
+    /// Время игры, когда началась текущая серия без столкновений.
+    streak_start: f64,
+    /// Сколько интервалов `STREAK_INTERVAL` текущей серии уже вознаграждено.
+    streak_intervals: u32,
+    /// Текущий множитель очков. Растёт, если астероиды уничтожаются один за другим.
+    multiplier: u32,
+    /// Время, оставшееся до сброса множителя очков.
+    combo_timer: f64,
+    /// Запас замедления времени в секундах.
+    slow_meter: f64,
+    /// Оставшееся время действия подобранного замедления.
+    slow_motion_timer: f64,
+    /// Оставшееся время действия подобранного множителя очков.
+    score_boost_timer: f64,
+    /// Таймер появления усилений.
+    power_up_timer: f64,
+    /// Усиления, которые ещё можно подобрать.
+    power_ups: Vec<PowerUp>,
+    /// Таймер появления гравитационных колодцев.
+    gravity_well_timer: f64,
+    /// Гравитационные колодцы, притягивающие астероиды.
+    gravity_wells: Vec<GravityWell>,
+    /// Таймер появления преследователей.
+    seeker_timer: f64,
+    /// Преследователи, летящие за кораблём.
+    seekers: Vec<Seeker>,
+    /// Вектор выпущенных пуль.
+    bullets: Vec<Bullet>,
+    /// Частицы взрывов и выхлопа двигателя.
+    particles: ParticleSystem,
+    /// Всплывающие надписи с начисленными очками.
+    popups: Vec<Popup>,
+    /// Звёздный фон.
+    starfield: Starfield,
+    /// Сетка для поиска астероидов рядом с кораблём. Заполняется заново каждый кадр.
+    #[serde(skip, default = "Game::empty_grid")]
+    grid: SpatialGrid,
+    /// Осколки астероидов, разбитых пулями за кадр. Память переиспользуется между кадрами.
+    #[serde(skip)]
+    fragments: Vec<Asteroid>,
+    /// Размеры окна, под которые рассчитаны положения объектов.
+    screen_size: Vec2,
+    /// Уровень сложности.
+    difficulty: Difficulty,
+    /// Настройки игровой механики.
+    config: GameConfig,
+    /// Назначение клавиш управления.
+    #[serde(skip)]
+    key_bindings: KeyBindings,
+    /// Зерно генератора случайных чисел, с которым началась игра.
+    seed: u64,
+    /// Генератор случайных чисел, определяющий появление астероидов.
+    rng: Rng,
+    /// Запись текущей игры.
+    recording: Replay,
+    /// Кадры воспроизводимой записи, если игра не управляется игроком.
+    /// Восстановленной игрой всегда управляет игрок.
+    #[serde(skip)]
+    playback: Option<std::vec::IntoIter<FrameInput>>,
+    /// Управляет ли кораблём автопилот (демонстрация на фоне меню).
+    autopilot: bool,
+    /// Идёт ли тренировка: астероиды появляются только по щелчку мыши,
+    /// время не идёт, а после столкновения корабль возвращается на место.
+    practice: bool,
+    /// Скорость астероидов, создаваемых в тренировке.
+    practice_speed: f32,
+    /// Был ли последний кадр пропущен из-за потери фокуса окна.
+    #[serde(skip)]
+    focus_lost: bool,
+    /// День ежедневного испытания (в виде числа `ГГГГММДД`), если идёт оно.
+    daily: Option<u32>,
+    /// Время, которое нужно продержаться, если это режим на время.
+    goal: Option<f64>,
+    /// Призрак лучшей игры с тем же зерном, если он есть.
+    ghost: Option<Ghost>,
+    /// Путь корабля в этой игре, из которого может получиться новый призрак.
+    track: Ghost,
+    /// Звуковые эффекты.
+    #[serde(skip)]
+    sounds: Sounds,
+    /// Звучит ли сейчас двигатель корабля.
+    #[serde(skip)]
+    thrust_sound: bool,
+    /// Отладочный оверлей.
+    #[serde(skip)]
+    overlay: DebugOverlay,
+    /// Радар с астероидами вокруг корабля.
+    #[serde(skip)]
+    radar: Radar,
+    /// Подстройка числа частиц и звёзд под частоту кадров.
+    #[serde(skip)]
+    quality: Quality,
+    /// Индикаторы нажатого управления для зрителей.
+    #[serde(skip)]
+    input_display: InputDisplay,
+    /// Индекс в пуле астероида, который раньше всех врежется в корабль, и время до удара.
+    /// Обновляется каждый кадр.
+    #[serde(skip)]
+    threat: Option<(usize, f32)>,
+    /// Оставшиеся бомбы.
+    bombs: u32,
+    /// Сколько раз игра продолжалась после гибели за очки.
+    continues: u32,
+    /// Предлагается ли продолжить игру после гибели. Пока игрок не решил, игра стоит.
+    continue_offer: bool,
+    /// Время мира, накопленное с последнего шага, но ещё не обработанное.
+    accumulator: f64,
+    /// Нажат ли выстрел после последнего шага.
+    fire_queued: bool,
+    /// Нажата ли бомба после последнего шага.
+    bomb_queued: bool,
+    /// Сила тряски экрана - наибольшее смещение изображения в пикселях.
+    shake: f32,
+}
+
+impl Game {
+    /// Длительность обратного отсчёта перед появлением астероидов.
+    const COUNTDOWN_DURATION: f64 = 3.0;
+    /// Интервал появления астероидов в начале игры.
+    const SPAWN_INTERVAL_START: f64 = 0.8;
+    /// Минимальный интервал появления астероидов, к которому он стремится со временем.
+    const SPAWN_INTERVAL_FLOOR: f64 = 0.15;
+    /// Во сколько раз разница между текущим и минимальным интервалом уменьшается
+    /// с каждой волной.
+    const WAVE_INTERVAL_DECAY: f64 = 0.85;
+    /// Время, через которое астероидов за раз появляется на один больше
+    /// (пока их число не достигнет наибольшего для уровня сложности).
+    const SPAWN_COUNT_PERIOD: f64 = 60.0;
+    /// Сколько раз пробуем создать астероид, не задевающий другие.
+    const SPAWN_ATTEMPTS: u32 = 5;
+    /// Наименьший зазор между новым астероидом и существующими.
+    const SPAWN_GAP: f32 = 10.0;
+    /// Число астероидов в первой волне.
+    const FIRST_WAVE_SIZE: u32 = 10;
+    /// На сколько астероидов каждая следующая волна больше предыдущей.
+    const WAVE_GROWTH: u32 = 5;
+    /// Длительность перерыва между волнами.
+    const WAVE_BREAK: f64 = 2.5;
+    /// Очки за прохождение волны, умножаемые на её номер.
+    const WAVE_SCORE: u64 = 500;
+    /// Наибольшая длительность кадра, учитываемая при обновлении игры.
+    /// За более долгий кадр астероиды могли бы пролететь сквозь корабль.
+    const MAX_ELAPSED_TIME: f64 = 0.05;
+    /// Кадр длиннее этого считается не подтормаживанием, а потерей фокуса окна
+    /// (окно свернули или переключились на другое), и игра ставится на паузу.
+    const FOCUS_LOSS_TIME: f64 = 0.5;
+    /// Длительность шага игрового мира.
+    pub const STEP: f64 = 1.0 / 120.0;
+    /// Очки за уничтожение астероида без множителя.
+    const KILL_SCORE: u64 = 100;
+    /// Время после уничтожения астероида, за которое следующее уничтожение увеличит множитель.
+    const COMBO_WINDOW: f64 = 1.5;
+    /// Путь в пикселях, за который начисляется одно очко. Чем быстрее летит корабль,
+    /// тем быстрее растут очки, поэтому форсаж рискован, но выгоден.
+    const DISTANCE_PER_POINT: f64 = 100.0;
+    /// Очки за пролёт астероида рядом с кораблём.
+    const NEAR_MISS_SCORE: u64 = 50;
+    /// Насколько близко к кораблю должен пролететь астероид, чтобы пролёт засчитался.
+    const NEAR_MISS_MARGIN: f32 = 15.0;
+    /// Наибольший множитель очков.
+    const MAX_MULTIPLIER: u32 = 8;
+    /// Наибольший запас замедления времени в секундах.
+    const SLOW_METER_MAX: f64 = 3.0;
+    /// Скорость восстановления запаса замедления (секунд запаса за секунду).
+    const SLOW_METER_RECHARGE: f64 = 0.25;
+    /// Во сколько раз замедляется время.
+    const SLOW_TIME_SCALE: f64 = 0.35;
+    /// Интервал появления усилений.
+    const POWER_UP_INTERVAL: f64 = 12.0;
+    /// Начальная скорость астероидов, создаваемых в тренировке.
+    const PRACTICE_SPEED_DEFAULT: f32 = 200.0;
+    /// Шаг изменения скорости астероидов в тренировке.
+    const PRACTICE_SPEED_STEP: f32 = 25.0;
+    /// Наибольшая скорость астероидов, создаваемых в тренировке.
+    const PRACTICE_SPEED_MAX: f32 = 600.0;
+    /// Интервал появления гравитационных колодцев.
+    const GRAVITY_WELL_INTERVAL: f64 = 30.0;
+    /// Интервал появления преследователей.
+    const SEEKER_INTERVAL: f64 = 40.0;
+    /// Длительность действия щита.
+    const SHIELD_DURATION: f32 = 5.0;
+    /// Длительность действия подобранного замедления.
+    const SLOW_MOTION_DURATION: f64 = 4.0;
+    /// Длительность действия множителя очков.
+    const SCORE_BOOST_DURATION: f64 = 8.0;
+    /// Во сколько раз множитель увеличивает очки.
+    const SCORE_BOOST: u64 = 2;
+    /// Размер ячейки сетки для поиска столкновений. Порядка размера крупного астероида.
+    const GRID_CELL_SIZE: f32 = 128.0;
+    /// Число бомб на одну игру.
+    const BOMB_COUNT: u32 = 3;
+    /// Радиус взрыва бомбы вокруг центра корабля.
+    const BOMB_RADIUS: f32 = 250.0;
+    /// Радиус вокруг корабля, в котором после удара разрушаются астероиды,
+    /// чтобы корабль не получил новый удар сразу после окончания неуязвимости.
+    const HIT_CLEAR_RADIUS: f32 = 150.0;
+    /// Масштаб значков жизней относительно корабля.
+    const LIFE_ICON_SCALE: f32 = 0.35;
+    /// Число частиц, скорость разлёта и время жизни частиц взрыва корабля при потере жизни.
+    const SHIP_EXPLOSION_SIZE: usize = 40;
+    const SHIP_EXPLOSION_SPEED: f32 = 300.0;
+    const SHIP_EXPLOSION_LIFETIME: f32 = 1.2;
+    /// Скорость, разброс скорости и время жизни частиц выхлопа при полной тяге.
+    const EXHAUST_SPEED: f32 = 150.0;
+    const EXHAUST_SPREAD: f32 = 30.0;
+    const EXHAUST_LIFETIME: f32 = 0.3;
+    /// Сила тряски экрана при столкновении корабля с астероидом.
+    const SHAKE_INTENSITY: f32 = 12.0;
+    /// Скорость затухания тряски экрана в пикселях в секунду.
+    const SHAKE_DECAY: f32 = 30.0;
+    /// Интервал серии без столкновений, за каждый из которых начисляются очки.
+    const STREAK_INTERVAL: f64 = 5.0;
+    /// Очки за первый интервал серии. За каждый следующий начисляется на столько же больше.
+    const STREAK_BONUS: u64 = 10;
+    /// Длительности серии в секундах, о которых сообщается отдельно.
+    const STREAK_MILESTONES: [u32; 3] = [10, 30, 60];
+    /// Сколько секунд показывается сообщение о длительности серии.
+    const STREAK_MILESTONE_TIME: f64 = 2.0;
+    /// Сколько раз за игру можно продолжить её после гибели.
+    const MAX_CONTINUES: u32 = 1;
+
+    /// Логика создания новой игры с заданным режимом управления кораблём,
+    /// поведением у краёв экрана, уровнем сложности, настройками механики и случайным зерном.
+    pub fn new(
+        flight_mode: FlightMode,
+        wrap_edges: bool,
+        difficulty: Difficulty,
+        config: GameConfig,
+        key_bindings: KeyBindings,
+        sounds: Sounds,
+    ) -> Self {
+        // Берём зерно из текущего времени, чтобы каждая игра была новой.
+        let seed = (miniquad::date::now() * 1000.0) as u64;
+        Self::with_seed(
+            flight_mode,
+            wrap_edges,
+            difficulty,
+            config,
+            key_bindings,
+            sounds,
+            seed,
+        )
+    }
+
+    /// Логика создания новой игры с заданным зерном генератора случайных чисел.
+    /// Игры с одинаковым зерном порождают одинаковую последовательность астероидов.
+    pub fn with_seed(
+        flight_mode: FlightMode,
+        wrap_edges: bool,
+        difficulty: Difficulty,
+        config: GameConfig,
+        key_bindings: KeyBindings,
+        sounds: Sounds,
+        seed: u64,
+    ) -> Self {
+        let mut game = Self::simulation(
+            flight_mode,
+            wrap_edges,
+            difficulty,
+            config,
+            seed,
+            Self::screen_size(),
+        );
+        game.key_bindings = key_bindings;
+        game.sounds = sounds;
+        game.last_update = get_time(); // Текущее время со старта приложения.
+        game
+    }
+
+    /// Логика создания игры без звука в окне размером `screen_size`.
+    ///
+    /// Такая игра не обращается к окну, часам и клавиатуре, пока её продвигают
+    /// через [`Game::advance`], поэтому её можно прогонять в тестах.
+    pub fn simulation(
+        flight_mode: FlightMode,
+        wrap_edges: bool,
+        difficulty: Difficulty,
+        config: GameConfig,
+        seed: u64,
+        screen_size: Vec2,
+    ) -> Self {
+        Self {
+            time: 0.0,
+            countdown: Self::COUNTDOWN_DURATION,
+            last_update: 0.0,
+            ship: Ship::new(
+                flight_mode,
+                wrap_edges,
+                difficulty.ship_config(config),
+                screen_size,
+            ),
+            asteroid_timer: 0.0,
+            waves: false,
+            wave: 1,
+            wave_budget: Self::wave_size(1),
+            wave_break: 0.0,
+            // Создаём пустой пул, способный вместить в себя
+            // до 100 астероидов без дополнительных аллокаций.
+            asteroids: Pool::with_capacity(100),
+            dodged: 0,
+            destroyed: 0,
+            score: 0,
+            distance_score: 0.0,
+            streak_start: 0.0,
+            streak_intervals: 0,
+            multiplier: 1,
+            combo_timer: 0.0,
+            slow_meter: Self::SLOW_METER_MAX,
+            slow_motion_timer: 0.0,
+            score_boost_timer: 0.0,
+            power_up_timer: 0.0,
+            power_ups: Vec::new(),
+            gravity_well_timer: 0.0,
+            gravity_wells: Vec::new(),
+            seeker_timer: 0.0,
+            seekers: Vec::new(),
+            bullets: Vec::new(),
+            particles: ParticleSystem::new(Quality::default().level().max_particles),
+            popups: Vec::new(),
+            starfield: Starfield::new(Quality::default().level().star_count, screen_size),
+            grid: Self::empty_grid(),
+            fragments: Vec::new(),
+            screen_size,
+            difficulty,
+            config,
+            key_bindings: KeyBindings::default(),
+            seed,
+            rng: Rng::new(seed),
+            recording: Replay::new(seed, flight_mode, wrap_edges, difficulty, false, false),
+            playback: None,
+            autopilot: false,
+            practice: false,
+            practice_speed: Self::PRACTICE_SPEED_DEFAULT,
+            focus_lost: false,
+            daily: None,
+            goal: None,
+            ghost: None,
+            track: Ghost::new(seed),
+            sounds: Sounds::default(),
+            thrust_sound: false,
+            overlay: DebugOverlay::default(),
+            radar: Radar::default(),
+            quality: Quality::default(),
+            input_display: InputDisplay::default(),
+            threat: None,
+            bombs: Self::BOMB_COUNT,
+            continues: 0,
+            continue_offer: false,
+            accumulator: 0.0,
+            fire_queued: false,
+            bomb_queued: false,
+            shake: 0.0,
+        }
+    }
+
+    /// Логика создания игры, воспроизводящей запись `replay`.
+    /// Запись повторяется точно, только если настройки механики `config` не менялись.
+    pub fn from_replay(
+        replay: Replay,
+        config: GameConfig,
+        key_bindings: KeyBindings,
+        sounds: Sounds,
+    ) -> Self {
+        let mut game = Self::with_seed(
+            replay.flight_mode,
+            replay.wrap_edges,
+            replay.difficulty,
+            config,
+            key_bindings,
+            sounds,
+            replay.seed,
+        );
+        if replay.waves {
+            game = game.with_waves();
+        }
+        if replay.vertical_movement {
+            game = game.with_vertical_movement();
+        }
+        if let Some(date) = replay.daily {
+            game = game.with_daily(date);
+        }
+        if let Some(goal) = replay.goal {
+            game = game.with_time_attack(goal);
+        }
+        game.playback = Some(replay.frames.into_iter());
+        game
+    }
+
+    /// Переключение игры на появление астероидов волнами. Каждая волна состоит
+    /// из определённого числа астероидов, а после того, как все они уничтожены или улетели,
+    /// наступает короткий перерыв перед следующей, более сложной волной.
+    pub fn with_waves(mut self) -> Self {
+        self.waves = true;
+        self.recording.waves = true;
+        self
+    }
+
+    /// Разрешение кораблю двигаться по вертикали в классическом режиме.
+    pub fn with_vertical_movement(mut self) -> Self {
+        self.ship.allow_vertical_movement();
+        self.recording.vertical_movement = true;
+        self
+    }
+
+    /// Заполнение экрана `count` астероидами, падающими вниз, и пропуск обратного отсчёта.
+    /// Астероиды расположены случайно выше середины экрана, поэтому корабль внизу
+    /// задевает их не сразу. Используется для замера скорости шага игрового мира.
+    pub fn with_asteroid_field(mut self, count: usize) -> Self {
+        self.countdown = 0.0;
+        let screen_size = self.screen_size;
+        for _ in 0..count {
+            let x = self.rng.gen_range(0.0, screen_size.x);
+            let y = self.rng.gen_range(-screen_size.y, screen_size.y / 2.0);
+            let speed = Vec2::new(self.rng.gen_range(-50.0, 50.0), 100.0);
+            let asteroid = Asteroid::with_motion(
+                &mut self.rng,
+                self.difficulty,
+                &self.config,
+                Vec2::new(x, y),
+                speed,
+            );
+            self.asteroids.spawn(asteroid);
+        }
+        self
+    }
+
+    /// Логика создания демонстрационной игры, в которой кораблём управляет автопилот.
+    /// Демонстрация идёт без звука и обратного отсчёта, не записывается
+    /// и не реагирует на клавиатуру.
+    pub fn demo(difficulty: Difficulty, config: GameConfig) -> Self {
+        let seed = (miniquad::date::now() * 1000.0) as u64;
+        let mut game = Self::with_seed(
+            FlightMode::Classic,
+            false,
+            difficulty,
+            config,
+            KeyBindings::default(),
+            Sounds::default(),
+            seed,
+        );
+        game.countdown = 0.0;
+        game.autopilot = true;
+        game
+    }
+
+    /// Переключение игры в тренировку. Астероиды, усиления и колодцы сами не появляются,
+    /// а время и очки за путь не идут. Поток не ускоряется, поэтому астероиды,
+    /// созданные щелчком мыши, всегда летят с заданной скоростью.
+    pub fn with_practice(mut self) -> Self {
+        self.practice = true;
+        self.countdown = 0.0;
+        self.config.vertical_acceleration = 0.0;
+        let recording = &self.recording;
+        self.ship = Ship::new(
+            recording.flight_mode,
+            recording.wrap_edges,
+            self.difficulty.ship_config(self.config),
+            self.screen_size,
+        );
+        if recording.vertical_movement {
+            self.ship.allow_vertical_movement();
+        }
+        self
+    }
+
+    /// Переключение игры в ежедневное испытание дня `date`: у корабля одна жизнь,
+    /// а усиления не появляются. Зерно игры должно быть получено из того же дня.
+    pub fn with_daily(mut self, date: u32) -> Self {
+        self.daily = Some(date);
+        self.recording.daily = Some(date);
+        self.ship.set_lives(1);
+        self
+    }
+
+    /// Переключение игры в режим на время: игра выиграна, если продержаться `goal` секунд.
+    pub fn with_time_attack(mut self, goal: f64) -> Self {
+        self.goal = Some(goal);
+        self.recording.goal = Some(goal);
+        self
+    }
+
+    /// Добавление призрака `ghost`, если он записан в игре с тем же зерном.
+    /// Призрак только отображается и ни с чем не сталкивается.
+    pub fn with_ghost(mut self, ghost: &Ghost) -> Self {
+        if ghost.seed() == self.seed {
+            self.ghost = Some(ghost.clone());
+        }
+        self
+    }
+
+    /// Путь корабля в этой игре.
+    pub fn track(&self) -> &Ghost {
+        &self.track
+    }
+
+    /// Уровень сложности.
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// День ежедневного испытания, если идёт оно.
+    pub fn daily(&self) -> Option<u32> {
+        self.daily
+    }
+
+    /// Идёт ли тренировка.
+    pub fn is_practice(&self) -> bool {
+        self.practice
+    }
+
+    /// Воспроизводит ли игра запись.
+    pub fn is_playback(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    /// Запись сыгранной игры.
+    pub fn into_replay(self) -> Replay {
+        self.recording
+    }
+
+    /// Логика обновления игрового процесса.
+    /// Если установлен `mouse_steering`, то корабль следует за курсором мыши.
+    /// Отклонение стика геймпада обрабатывается согласно `response`.
+    pub fn update(
+        &mut self,
+        gamepad: &Gamepad,
+        mouse_steering: bool,
+        response: AxisResponse,
+    ) -> Option<RunStats> {
+        // В отладочной сборке F5 сохраняет состояние игры, а F9 - восстанавливает его.
+        #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+        if !self.autopilot {
+            if is_key_pressed(KeyCode::F5) {
+                self.save_state(STATE_FILE);
+            }
+            if is_key_pressed(KeyCode::F9) {
+                self.load_state(STATE_FILE);
+            }
+        }
+
+        let resized = self.handle_resize();
+        self.overlay.update();
+        self.radar.update();
+        self.input_display.update();
+        if self.practice {
+            self.update_practice();
+        }
+        // Тряска экрана затухает по реальному времени, независимо от замедления.
+        self.shake = (self.shake - Self::SHAKE_DECAY * get_frame_time()).max(0.0);
+
+        // Пока предлагается продолжить игру, она стоит и ждёт решения.
+        if self.continue_offer {
+            return self.update_continue_offer(gamepad);
+        }
+
+        // Измеряем длительность кадра один раз, в начале обновления.
+        let elapsed_time = self.elapsed_time();
+        // Очень долгий кадр означает, что окно теряло фокус: такой кадр не обрабатываем,
+        // а просим поставить игру на паузу. Исключение - кадр, в котором изменился размер окна:
+        // пока окно растягивают или переключают полноэкранный режим, кадры задерживаются намеренно.
+        if elapsed_time > Self::FOCUS_LOSS_TIME && !resized {
+            self.focus_lost = true;
+            return None;
+        }
+        // Обычные подтормаживания просто замедляют игру: длительность кадра
+        // ограничивается в `advance`. Они же учитываются при подстройке качества, а кадры после потери фокуса - нет.
+        if let Some(level) = self.quality.update(get_fps() as f32, get_frame_time()) {
+            self.starfield.set_count(level.star_count, self.screen_size);
+            self.particles.set_max(level.max_particles);
+        }
+
+        // Получаем управление и длительность кадра: из записи, если она воспроизводится,
+        // иначе - от автопилота или с клавиатуры и геймпада, а длительность - с часов.
+        let frame = match &mut self.playback {
+            Some(frames) => match frames.next() {
+                Some(frame) => frame,
+                None => return Some(self.finish()), // Запись закончилась.
+            },
+            None if self.autopilot => FrameInput {
+                input: autopilot::input(&self.ship, &self.asteroids, self.screen_size.x),
+                elapsed_time,
+            },
+            None => {
+                let frame = FrameInput {
+                    input: Input::read(&self.key_bindings, gamepad, mouse_steering, response),
+                    elapsed_time,
+                };
+                self.recording.frames.push(frame);
+                frame
+            }
+        };
+        self.input_display.show(frame.input, get_frame_time());
+        if let Some(stats) = self.advance(frame) {
+            return Some(stats);
+        }
+
+        // Двигатель слышен, пока игрок управляет кораблём.
+        self.set_thrust_sound(frame.input.steer != 0.0 || frame.input.thrust);
+
+        None // Игра продолжается.
+    }
+
+    /// Продвижение игры на кадр `frame`: отсчёт времени и шаги игрового мира.
+    /// Кадр длиннее `MAX_ELAPSED_TIME` продвигает игру только на `MAX_ELAPSED_TIME`.
+    /// Возвращает итоги игры, если она завершилась.
+    pub fn advance(&mut self, frame: FrameInput) -> Option<RunStats> {
+        let input = frame.input;
+        let elapsed_time = frame.elapsed_time.min(Self::MAX_ELAPSED_TIME);
+        // Пока идёт обратный отсчёт, время игры стоит, а астероиды и усиления не появляются.
+        // Корабль при этом уже можно двигать.
+        let warming_up = self.countdown > 0.0;
+        if warming_up {
+            self.countdown -= elapsed_time;
+        } else if !self.practice {
+            // Время игры идёт с обычной скоростью и при замедлении,
+            // чтобы оно не давало очков даром.
+            self.time += elapsed_time;
+        }
+        // В режиме на время игра выиграна, как только прошло заданное время.
+        if let Some(goal) = self.goal.filter(|&goal| self.time >= goal) {
+            self.time = goal;
+            return Some(self.finish());
+        }
+        // Игровой мир обновляется шагами постоянной длительности: так движение и столкновения
+        // не зависят от частоты кадров. Замедление времени уменьшает накопленное время мира.
+        let time_scale = self.time_scale(input.slow, elapsed_time);
+        self.accumulator += elapsed_time * time_scale;
+        // Нажатия выстрела и бомбы срабатывают один раз, в первом же шаге.
+        self.fire_queued |= input.fire;
+        self.bomb_queued |= input.bomb;
+        while self.accumulator >= Self::STEP {
+            self.accumulator -= Self::STEP;
+            let step_input = Input {
+                fire: std::mem::take(&mut self.fire_queued),
+                bomb: std::mem::take(&mut self.bomb_queued),
+                ..input
+            };
+            if let Some(stats) = self.step(step_input, Self::STEP) {
+                return Some(stats);
+            }
+            // После гибели с предложением продолжить мир дальше не продвигается.
+            if self.continue_offer {
+                break;
+            }
+        }
+        self.track
+            .record(self.time, self.ship.position(), self.ship.rotation());
+        self.threat = self.find_threat();
+        None
+    }
+
+    /// Поиск астероида, который раньше всех врежется в корабль, если они не изменят
+    /// скорости. Возвращает его индекс в пуле и время до удара.
+    fn find_threat(&self) -> Option<(usize, f32)> {
+        let (center, velocity) = (self.ship.center(), self.ship.velocity());
+        // Относительно экрана астероиды ещё и летят вниз со скоростью корабля.
+        let fall = Vec2::new(0.0, self.ship.smoothed_vertical_speed());
+        self.asteroids
+            .iter()
+            .filter_map(|(index, asteroid)| {
+                let offset = asteroid.position() - center;
+                let relative = asteroid.speed() + fall - velocity;
+                let reach = asteroid.radius() + self.ship.collision_radius();
+                let time = threat::time_to_impact(offset, relative, reach)?;
+                (time <= threat::HORIZON).then_some((index, time))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+
+    /// Шаг игрового мира длительностью `elapsed_time` с управлением `input`.
+    /// Возвращает итоги игры, если она завершилась.
+    fn step(&mut self, input: Input, elapsed_time: f64) -> Option<RunStats> {
+        let warming_up = self.countdown > 0.0;
+        // В тренировке ничего не появляется само, а очки за путь не начисляются.
+        if !warming_up && !self.practice {
+            self.asteroid_timer += elapsed_time; // Обновляем таймер появления астероидов.
+            self.power_up_timer += elapsed_time; // и усилений,
+            self.gravity_well_timer += elapsed_time; // гравитационных колодцев
+            self.seeker_timer += elapsed_time; // и преследователей.
+
+            // Начисляем очки за путь, пройденный с текущей скоростью.
+            let distance = self.ship.vertical_speed() as f64 * elapsed_time;
+            self.distance_score += distance / Self::DISTANCE_PER_POINT;
+            let points = self.distance_score.floor();
+            self.score += points as u64 * self.score_boost();
+            self.distance_score -= points;
+
+            self.update_streak();
+        }
+        // В режиме волн астероиды появляются, только пока не исчерпано число астероидов волны.
+        let spawning = !self.waves || (self.wave_budget > 0 && self.wave_break <= 0.0);
+        if spawning && self.asteroid_timer > self.spawn_interval() {
+            // Если астероиды не появлялись дольше текущего интервала,
+            self.asteroid_timer = 0.0; // сбрасываем таймер
+                                       // и создаём новые астероиды, но в режиме волн не больше, чем осталось в волне.
+            let count = if self.waves {
+                self.spawn_count().min(self.wave_budget)
+            } else {
+                self.spawn_count()
+            };
+            let spawned = (0..count).filter(|_| self.spawn_asteroid()).count() as u32;
+            self.wave_budget = self.wave_budget.saturating_sub(spawned);
+        }
+
+        // Забываем астероиды, вышедшие за пределы экрана, и считаем их пролетевшими мимо.
+        let before = self.asteroids.len();
+        let screen_size = self.screen_size;
+        self.asteroids
+            .retain(|asteroid| !asteroid.out_of_bounds(screen_size.x, screen_size.y));
+        self.dodged += (before - self.asteroids.len()) as u32;
+
+        if self.waves && !warming_up {
+            self.update_waves(elapsed_time);
+        }
+
+        // Время от времени сверху падает усиление, но только не в ежедневном испытании.
+        if self.daily.is_none() && self.power_up_timer > Self::POWER_UP_INTERVAL {
+            self.power_up_timer = 0.0;
+            let kind = PowerUpKind::random(&mut self.rng);
+            let power_up = PowerUp::new(&mut self.rng, kind, screen_size.x);
+            self.power_ups.push(power_up);
+        }
+
+        // Двигаем усиления, подбираем те, которых коснулся корабль,
+        // и забываем вылетевшие за пределы экрана.
+        for power_up in &mut self.power_ups {
+            power_up.update(elapsed_time, self.ship.smoothed_vertical_speed());
+        }
+        let ship = &mut self.ship;
+        let mut collected = Vec::new();
+        self.power_ups.retain(|power_up| {
+            if ship.is_collapse(power_up.position(), PowerUp::RADIUS) {
+                collected.push(power_up.kind());
+                return false;
+            }
+            !power_up.out_of_bounds(screen_size.x, screen_size.y)
+        });
+        for kind in collected {
+            self.collect_power_up(kind);
+        }
+        self.score_boost_timer = (self.score_boost_timer - elapsed_time).max(0.0);
+
+        // Изредка сверху надвигается гравитационный колодец.
+        if self.gravity_well_timer > Self::GRAVITY_WELL_INTERVAL {
+            self.gravity_well_timer = 0.0;
+            let well = GravityWell::new(&mut self.rng, screen_size.x);
+            self.gravity_wells.push(well);
+        }
+        for well in &mut self.gravity_wells {
+            well.update(elapsed_time, self.ship.smoothed_vertical_speed());
+        }
+        self.gravity_wells
+            .retain(|well| !well.out_of_bounds(screen_size.y));
+
+        // Изредка появляется преследователь, летящий за кораблём, пока не истечёт его время.
+        if self.seeker_timer > Self::SEEKER_INTERVAL {
+            self.seeker_timer = 0.0;
+            let seeker = Seeker::new(&mut self.rng, screen_size.x);
+            self.seekers.push(seeker);
+        }
+        let target = self.ship.center();
+        for seeker in &mut self.seekers {
+            seeker.update(elapsed_time, target);
+        }
+        self.seekers.retain(Seeker::is_alive);
+
+        if input.bomb && self.bombs > 0 {
+            // Бомба уничтожает астероиды, центры которых попали в радиус взрыва.
+            // Усиления при этом остаются на месте.
+            self.bombs -= 1;
+            let center = self.ship.center();
+            let particles = &mut self.particles;
+            let destroyed = &mut self.destroyed;
+            self.asteroids.retain(|asteroid| {
+                if asteroid.position().distance(center) > Self::BOMB_RADIUS {
+                    return true;
+                }
+                particles.explosion(asteroid.position(), ORANGE);
+                *destroyed += 1;
+                false
+            });
+            self.sounds.play_explosion();
+            self.shake = Self::SHAKE_INTENSITY;
+        }
+
+        // Если нажата клавиша выстрела и орудие перезарядилось, корабль выпускает пулю.
+        if let Some(bullet) = self.ship.fire(elapsed_time, input.fire) {
+            self.bullets.push(bullet);
+            self.sounds.play_shoot();
+        }
+
+        // Обновляем состояние пуль и забываем те, что вылетели за пределы экрана.
+        for bullet in &mut self.bullets {
+            bullet.update(elapsed_time);
+        }
+        self.bullets
+            .retain(|bullet| !bullet.out_of_bounds(screen_size.x, screen_size.y));
+
+        // Пули, попавшие в астероиды, исчезают, а астероиды получают повреждения.
+        // Разрушенные астероиды исчезают, а крупные при этом раскалываются на осколки.
+        let bullets = &mut self.bullets;
+        let particles = &mut self.particles;
+        let sounds = &self.sounds;
+        let rng = &mut self.rng;
+        let mut fragments = std::mem::take(&mut self.fragments);
+        let mut kills = 0;
+        self.asteroids.retain_mut(|asteroid| {
+            let hit = bullets
+                .iter()
+                .position(|bullet| asteroid.contains(bullet.position()));
+            match hit {
+                Some(index) => {
+                    bullets.swap_remove(index);
+                    if !asteroid.hit() {
+                        return true; // Бронированный астероид выдержал попадание.
+                    }
+                    fragments.extend(asteroid.fragment(rng, &self.config));
+                    particles.explosion(asteroid.position(), LIGHTGRAY);
+                    sounds.play_explosion();
+                    kills += 1;
+                    false
+                }
+                None => true,
+            }
+        });
+        // Осколки появляются на месте астероида, уже проверенного на выход за границы,
+        // поэтому в этом кадре они заведомо не будут забыты.
+        self.spawn_fragments(fragments.drain(..));
+        self.fragments = fragments;
+
+        // Множитель очков сбрасывается, если долго не уничтожать астероиды.
+        self.combo_timer -= elapsed_time;
+        if self.combo_timer <= 0.0 {
+            self.multiplier = 1;
+        }
+        for _ in 0..kills {
+            self.register_kill();
+        }
+
+        // Прокручиваем звёздный фон со скоростью полёта корабля.
+        self.starfield.update(
+            elapsed_time,
+            self.ship.smoothed_vertical_speed(),
+            screen_size,
+        );
+
+        // Обновляем частицы и забываем угасшие. Если частиц больше, чем позволяет
+        // качество, раньше срока гаснут самые старые.
+        self.particles.update(elapsed_time);
+
+        // Обновляем всплывающие надписи и забываем угасшие.
+        for popup in &mut self.popups {
+            popup.update(elapsed_time);
+        }
+        self.popups.retain(|popup| popup.is_alive());
+
+        // Колодцы притягивают астероиды, искривляя их путь.
+        for well in &self.gravity_wells {
+            for asteroid in self.asteroids.iter_mut() {
+                asteroid.accelerate(well.acceleration(asteroid.position()), elapsed_time);
+            }
+        }
+
+        // Обновляем состояние астероиндов.
+        for asteroid in self.asteroids.iter_mut() {
+            asteroid.update(elapsed_time, self.ship.smoothed_vertical_speed());
+        }
+
+        // Раскладываем астероиды по ячейкам сетки для поиска столкновений.
+        self.grid.clear();
+        for (index, asteroid) in self.asteroids.iter() {
+            self.grid
+                .insert(index, asteroid.position(), asteroid.radius());
+        }
+
+        #[cfg(feature = "asteroid-collisions")]
+        self.collide_asteroids(elapsed_time);
+
+        // Неуязвимый корабль не сталкивается с астероидами.
+        if !self.ship.is_invulnerable() {
+            // Проверяем только астероиды из ячеек сетки рядом с кораблём.
+            let (min, max) = self.ship.bounds();
+            let ship = &self.ship;
+            let asteroids = &self.asteroids;
+            // Сетка возвращает астероиды в произвольном порядке, а сталкивается корабль
+            // с первым из них, как и при переборе.
+            let collided = self
+                .grid
+                .query_region(min, max)
+                .filter(|&index| {
+                    let asteroid = &asteroids[index];
+                    ship.is_collapse(asteroid.position(), asteroid.radius())
+                })
+                .min();
+
+            if let Some(index) = collided {
+                // Если астероид столкнулся с кораблём, то он разрушается,
+                let (position, fragments) = {
+                    let asteroid = &self.asteroids[index];
+                    (
+                        asteroid.position(),
+                        asteroid.fragment(&mut self.rng, &self.config),
+                    )
+                };
+                self.asteroids.release(index);
+                self.spawn_fragments(fragments);
+                // а корабль получает удар.
+                if let Some(stats) = self.hit_ship(position) {
+                    return Some(stats);
+                }
+            }
+        }
+
+        // Преследователь, догнавший корабль, взрывается, а корабль получает удар.
+        if !self.ship.is_invulnerable() {
+            let ship = &self.ship;
+            let caught = self
+                .seekers
+                .iter()
+                .position(|seeker| ship.is_collapse(seeker.position(), Seeker::RADIUS));
+            if let Some(index) = caught {
+                let seeker = self.seekers.swap_remove(index);
+                if let Some(stats) = self.hit_ship(seeker.position()) {
+                    return Some(stats);
+                }
+            }
+        }
+
+        self.detect_near_misses();
+
+        // На высокой сложности колодцы притягивают и корабль.
+        if self.difficulty.gravity_pulls_ship() {
+            for well in &self.gravity_wells {
+                let acceleration = well.acceleration(self.ship.center());
+                self.ship.accelerate(acceleration, elapsed_time);
+            }
+        }
+
+        // Обновляем состояние корабля.
+        self.ship.update(input, elapsed_time, self.screen_size);
+        // Пока работает двигатель, из-за основания корабля вылетают частицы выхлопа.
+        if let Some((nozzle, direction)) = self.ship.exhaust() {
+            // Разброс берётся из генератора macroquad, чтобы не влиять на запись игры.
+            let spread = Vec2::new(rand::gen_range(-1.0, 1.0), rand::gen_range(-1.0, 1.0));
+            let velocity = direction * Self::EXHAUST_SPEED + spread * Self::EXHAUST_SPREAD;
+            let exhaust = Particle::new(nozzle, velocity, Self::EXHAUST_LIFETIME, 2.0, ORANGE);
+            self.particles.emit(exhaust);
+        }
+
+        None
+    }
+
+    /// Отображаем игру с текстом на языке `lang` в цветах темы `theme`.
+    pub fn draw(&self, best_time: f64, lang: Lang, theme: &Theme) {
+        // При тряске сдвигаем изображение игрового мира, не трогая положения объектов.
+        // В отладочном отдалённом виде тряски нет.
+        let bounds = WorldBounds {
+            screen_size: self.screen_size,
+            spawn_y: Asteroid::spawn_y(self.difficulty),
+            margin: Asteroid::BOUNDS_MARGIN,
+        };
+        match self.overlay.zoomed_out_camera(&bounds) {
+            Some(camera) => set_camera(&camera),
+            None => self.set_shake_camera(),
+        }
+        // Объекты отображаются между положениями до и после последнего шага мира
+        // в соответствии с накопленным, но ещё не обработанным временем.
+        // Так движение плавное, сколько бы шагов ни пришлось на кадр.
+        let alpha = (self.accumulator / Self::STEP) as f32;
+
+        // Фон и звёзды отображаем первыми, позади всего остального.
+        if let Some(texture) = assets::textures().background {
+            let center = self.screen_size / 2.0;
+            assets::draw_centered(texture, center, self.screen_size, 0.0, WHITE);
+        }
+        self.starfield.draw();
+
+        // Колодцы полупрозрачны и отображаются позади остальных объектов.
+        for well in &self.gravity_wells {
+            well.draw(alpha);
+        }
+
+        for seeker in &self.seekers {
+            seeker.draw(theme, alpha);
+        }
+
+        // Призрак лучшей игры отображаем под кораблём.
+        if let Some(ghost) = &self.ghost {
+            ghost.draw(self.game_time(), theme.ship);
+        }
+
+        self.ship.draw(theme, alpha); // Отображаем корабль.
+        if self.overlay.is_visible() {
+            self.ship.draw_hitbox(alpha); // и при отладке - область, опасную для него.
+        }
+
+        // Отображаем пули.
+        for bullet in &self.bullets {
+            bullet.draw(alpha);
+        }
+
+        // Отображаем астероиды и предупреждения о тех, что вот-вот появятся сверху.
+        for (_, asteroid) in self.asteroids.iter() {
+            asteroid.draw(theme, alpha);
+            asteroid.draw_entry_warning(self.ship.smoothed_vertical_speed());
+        }
+
+        // Ближайшую угрозу обводим, а у корабля показываем указатель на неё.
+        if let Some((index, time)) = self.threat {
+            let asteroid = &self.asteroids[index];
+            asteroid.draw_threat_outline(alpha);
+            threat::draw_indicator(self.ship.center(), asteroid.position(), time);
+        }
+
+        // Отображаем усиления.
+        for power_up in &self.power_ups {
+            power_up.draw(theme, alpha);
+        }
+
+        // Отображаем частицы взрывов и выхлопа.
+        self.particles.draw();
+
+        // Отображаем всплывающие надписи.
+        for popup in &self.popups {
+            popup.draw();
+        }
+
+        self.overlay.draw_bounds(&bounds);
+
+        // Интерфейс отображаем без тряски.
+        set_default_camera();
+        self.draw_time(best_time, lang, theme); // Отображаем текст с лучшим и текущим временем.
+
+        // Помечаем воспроизведение записи.
+        if self.is_playback() {
+            draw_ui_text(lang.text(TextKey::Replay), 0.0, 24.0, 24.0, RED);
+        }
+
+        // Помечаем ежедневное испытание строкой ниже, чтобы не закрыть пометку записи.
+        if self.daily.is_some() {
+            draw_ui_text(lang.text(TextKey::Daily), 0.0, 48.0, 24.0, RED);
+        }
+
+        // В тренировке помечаем её и подсказываем управление.
+        if self.practice {
+            draw_ui_text(lang.text(TextKey::Practice), 0.0, 24.0, 24.0, RED);
+            let text = lang.format(TextKey::PracticeHelp, self.practice_speed);
+            draw_centered_text(&text, 20.0, -screen_height() / 2.0 + 30.0, theme.text);
+        }
+
+        // Полосы запаса замедления и форсажа в правом нижнем углу.
+        let slow = (self.slow_meter / Self::SLOW_METER_MAX) as f32;
+        Self::draw_meter(lang.text(TextKey::Slow), slow, SKYBLUE, 0, theme);
+        Self::draw_meter(
+            lang.text(TextKey::Boost),
+            self.ship.boost_fraction(),
+            ORANGE,
+            1,
+            theme,
+        );
+
+        // Во время обратного отсчёта показываем крупные цифры 3, 2, 1.
+        if self.countdown > 0.0 {
+            let text = format!("{}", self.countdown.ceil() as u32);
+            draw_centered_text(&text, 120.0, 0.0, theme.selection);
+        }
+
+        // После гибели предлагаем продолжить игру и подсказываем клавиши.
+        if self.continue_offer {
+            let text = lang.format(TextKey::ContinueOffer, self.continue_cost());
+            draw_centered_text(&text, 48.0, -60.0, theme.selection);
+            let text = format!(
+                "{:?} - {}, {:?} - {}",
+                self.key_bindings.start,
+                lang.text(TextKey::ContinueYes),
+                self.key_bindings.quit,
+                lang.text(TextKey::ContinueNo)
+            );
+            draw_centered_text(&text, 24.0, -20.0, theme.text);
+        }
+
+        // Недолго после того, как серия без столкновений достигла круглой длительности,
+        // мигающей надписью сообщаем о ней.
+        let streak = self.streak();
+        let milestone = Self::STREAK_MILESTONES.iter().rev().find(|&&milestone| {
+            let since = streak - milestone as f64;
+            (0.0..Self::STREAK_MILESTONE_TIME).contains(&since)
+        });
+        if let Some(&milestone) = milestone {
+            let mut color = theme.record;
+            color.a = 0.6 + 0.4 * (streak as f32 * 10.0).sin();
+            let text = lang.format(TextKey::StreakMilestone, milestone);
+            draw_centered_text(&text, 36.0, 80.0, color);
+        }
+
+        // Во время перерыва между волнами сообщаем о пройденной волне.
+        if self.waves && self.wave_break > 0.0 {
+            let text = lang.format(TextKey::WaveCleared, self.wave);
+            draw_centered_text(&text, 48.0, -60.0, theme.selection);
+        }
+
+        // В правом верхнем углу столбиком показываем значки действующих усилений
+        // и оставшееся время их действия.
+        let effects = [
+            (PowerUpKind::Shield, self.ship.shield_time() as f64),
+            (PowerUpKind::SlowMotion, self.slow_motion_timer),
+            (PowerUpKind::ScoreBoost, self.score_boost_timer),
+        ];
+        let active = effects.into_iter().filter(|&(_, time)| time > 0.0);
+        for (row, (kind, time)) in active.enumerate() {
+            let (x, y) = (screen_width() - 70.0, 20.0 + 28.0 * row as f32);
+            draw_circle(x, y, 10.0, kind.color());
+            draw_ui_text(&format!("{:.1}", time), x + 16.0, y + 8.0, 24.0, theme.text);
+        }
+
+        let asteroids = self.asteroids.iter();
+        let asteroids = asteroids.map(|(_, asteroid)| (asteroid.position(), asteroid.radius()));
+        self.radar
+            .draw(self.ship.center(), asteroids, self.screen_size, theme);
+        self.input_display.draw(theme);
+
+        let quality = self.quality.level();
+        self.overlay.draw(
+            ObjectCounts {
+                asteroids: self.asteroids.len(),
+                particles: self.particles.len(),
+            },
+            QualityInfo {
+                name: quality.name,
+                max_particles: quality.max_particles,
+            },
+        );
+    }
+
+    /// Был ли последний кадр пропущен из-за потери фокуса окна. Тогда игру нужно
+    /// поставить на паузу. Признак сбрасывается при проверке.
+    pub fn take_focus_lost(&mut self) -> bool {
+        std::mem::take(&mut self.focus_lost)
+    }
+
+    /// Постановка игры на паузу.
+    pub fn pause(&mut self) {
+        self.set_thrust_sound(false); // Двигатель на паузе не слышен.
+    }
+
+    /// Сохранение состояния игры в файл `path`. Ошибка записи не прерывает игру.
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    pub fn save_state(&self, path: &str) {
+        let result = serde_json::to_string(self)
+            .map_err(|err| err.to_string())
+            .and_then(|text| std::fs::write(path, text).map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            warn!("Failed to save game state to {}: {}", path, err);
+        }
+    }
+
+    /// Восстановление состояния игры из файла `path`. Если файла нет или он повреждён,
+    /// игра продолжается как была.
+    ///
+    /// Таймеры в состоянии хранятся как оставшееся или прошедшее время, поэтому игра
+    /// продолжается с момента сохранения. Размер окна при сохранении тоже хранится,
+    /// и если он с тех пор изменился, объекты перенесутся как при изменении размера окна.
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    pub fn load_state(&mut self, path: &str) {
+        let state = std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| serde_json::from_str::<Game>(&text).map_err(|err| err.to_string()));
+        let state = match state {
+            Ok(state) => state,
+            Err(err) => {
+                warn!("Failed to load game state from {}: {}", path, err);
+                return;
+            }
+        };
+        self.set_thrust_sound(false);
+        *self = Game {
+            last_update: get_time(),
+            key_bindings: self.key_bindings,
+            sounds: self.sounds,
+            overlay: std::mem::take(&mut self.overlay),
+            radar: std::mem::take(&mut self.radar),
+            quality: std::mem::take(&mut self.quality),
+            input_display: std::mem::take(&mut self.input_display),
+            ..state
+        };
+        self.particles.set_max(self.quality.level().max_particles);
+    }
+
+    /// Пустая сетка поиска астероидов.
+    fn empty_grid() -> SpatialGrid {
+        SpatialGrid::new(Self::GRID_CELL_SIZE)
+    }
+
+    /// Снятие игры с паузы.
+    pub fn resume(&mut self) {
+        // На паузе время игры стоит, поэтому не учитываем время, проведённое на ней,
+        // чтобы после паузы не было скачка.
+        self.last_update = get_time();
+    }
+
+    /// Завершение игры. Возвращает её итоги.
+    pub fn finish(&mut self) -> RunStats {
+        self.set_thrust_sound(false);
+        RunStats {
+            time: self.game_time(),
+            dodged: self.dodged,
+            destroyed: self.destroyed,
+            waves_cleared: self.waves.then(|| self.waves_cleared()),
+            score: self.score,
+            goal: self.goal,
+            won: self.goal.is_some_and(|goal| self.time >= goal),
+            lives: self.ship.lives(),
+            difficulty: self.difficulty,
+            daily: self.daily(),
+            seed: self.seed,
+        }
+    }
+
+    /// Управление тренировкой мышью: щелчок создаёт астероид в точке курсора,
+    /// летящий вниз, а колесо мыши меняет скорость следующих астероидов.
+    fn update_practice(&mut self) {
+        let (_, wheel) = mouse_wheel();
+        if wheel != 0.0 {
+            let step = Self::PRACTICE_SPEED_STEP * wheel.signum();
+            self.practice_speed = (self.practice_speed + step)
+                .clamp(Self::PRACTICE_SPEED_STEP, Self::PRACTICE_SPEED_MAX);
+        }
+
+        if is_mouse_button_pressed(MouseButton::Left) {
+            let position = Vec2::from(mouse_position());
+            let speed = Vec2::new(0.0, self.practice_speed);
+            let (difficulty, config) = (self.difficulty, &self.config);
+            let asteroid =
+                Asteroid::with_motion(&mut self.rng, difficulty, config, position, speed);
+            self.asteroids.spawn(asteroid);
+        }
+    }
+
+    /// Добавление осколков `fragments`, пока астероидов не больше `config.max_asteroids`.
+    fn spawn_fragments(&mut self, fragments: impl IntoIterator<Item = Asteroid>) {
+        let room = self
+            .config
+            .max_asteroids
+            .saturating_sub(self.asteroids.len());
+        for fragment in fragments.into_iter().take(room) {
+            self.asteroids.spawn(fragment);
+        }
+    }
+
+    /// Смена волн. Когда все астероиды волны появились и исчезли, волна считается пройденной
+    /// и начинается перерыв, а после него - следующая волна.
+    fn update_waves(&mut self, elapsed_time: f64) {
+        if self.wave_break > 0.0 {
+            self.wave_break -= elapsed_time;
+            if self.wave_break <= 0.0 {
+                self.wave += 1;
+                self.wave_budget = Self::wave_size(self.wave);
+                self.asteroid_timer = 0.0;
+            }
+        } else if self.wave_budget == 0 && self.asteroids.is_empty() {
+            self.score += Self::WAVE_SCORE * self.wave as u64 * self.score_boost();
+            self.wave_break = Self::WAVE_BREAK;
+        }
+    }
+
+    /// Число астероидов в волне с номером `wave`.
+    fn wave_size(wave: u32) -> u32 {
+        Self::FIRST_WAVE_SIZE + Self::WAVE_GROWTH * (wave - 1)
+    }
+
+    /// Число пройденных волн. Во время перерыва текущая волна уже пройдена.
+    fn waves_cleared(&self) -> u32 {
+        if self.wave_break > 0.0 {
+            self.wave
+        } else {
+            self.wave - 1
+        }
+    }
+
+    /// Применение подобранного усиления вида `kind`. Повторно подобранное усиление
+    /// продлевает действие.
+    fn collect_power_up(&mut self, kind: PowerUpKind) {
+        match kind {
+            PowerUpKind::Shield => self.ship.activate_shield(Self::SHIELD_DURATION),
+            PowerUpKind::SlowMotion => {
+                self.slow_motion_timer = self.slow_motion_timer.max(Self::SLOW_MOTION_DURATION)
+            }
+            PowerUpKind::ScoreBoost => {
+                self.score_boost_timer = self.score_boost_timer.max(Self::SCORE_BOOST_DURATION)
+            }
+        }
+    }
+
+    /// Во сколько раз сейчас увеличиваются начисляемые очки.
+    fn score_boost(&self) -> u64 {
+        if self.score_boost_timer > 0.0 {
+            Self::SCORE_BOOST
+        } else {
+            1
+        }
+    }
+
+    /// Начисление очков за уничтожение астероида.
+    /// Если предыдущий астероид был уничтожен недавно, то множитель очков растёт,
+    /// а таймер его сброса запускается заново.
+    fn register_kill(&mut self) {
+        if self.combo_timer > 0.0 {
+            self.multiplier = (self.multiplier + 1).min(Self::MAX_MULTIPLIER);
+        } else {
+            self.multiplier = 1;
+        }
+        self.combo_timer = Self::COMBO_WINDOW;
+        self.score += Self::KILL_SCORE * self.multiplier as u64 * self.score_boost();
+        self.destroyed += 1;
+    }
+
+    /// Начисление очков за астероиды, пролетевшие совсем рядом с кораблём, но не задевшие его.
+    /// Каждый астероид засчитывается не больше одного раза, а над кораблём всплывает
+    /// надпись с начисленными очками.
+    fn detect_near_misses(&mut self) {
+        let points = Self::NEAR_MISS_SCORE * self.score_boost();
+        let ship = &self.ship;
+        for asteroid in self.asteroids.iter_mut() {
+            let (position, radius) = (asteroid.position(), asteroid.radius());
+            let close = ship.is_collapse(position, radius + Self::NEAR_MISS_MARGIN);
+            let touched = close && ship.is_collapse(position, radius);
+            if asteroid.track_near_miss(close, touched) {
+                self.score += points;
+                self.popups.push(Popup::new(points, ship.center()));
+            }
+        }
+    }
+
+    /// Длительность текущей серии без столкновений.
+    fn streak(&self) -> f64 {
+        self.time - self.streak_start
+    }
+
+    /// Начисление очков за серию без столкновений: за каждые `STREAK_INTERVAL` секунд
+    /// серии, и чем дольше она длится, тем больше. Над кораблём всплывает надпись.
+    fn update_streak(&mut self) {
+        let intervals = (self.streak() / Self::STREAK_INTERVAL) as u32;
+        while self.streak_intervals < intervals {
+            self.streak_intervals += 1;
+            let bonus = Self::STREAK_BONUS * self.streak_intervals as u64 * self.score_boost();
+            self.score += bonus;
+            self.popups.push(Popup::new(bonus, self.ship.center()));
+        }
+    }
+
+    /// Начало новой серии без столкновений.
+    fn reset_streak(&mut self) {
+        self.streak_start = self.time;
+        self.streak_intervals = 0;
+    }
+
+    /// Разрушение астероидов ближе `HIT_CLEAR_RADIUS` к кораблю без осколков и очков.
+    fn clear_around_ship(&mut self) {
+        let center = self.ship.center();
+        let particles = &mut self.particles;
+        self.asteroids.retain(|asteroid| {
+            let near = asteroid.position().distance(center) < Self::HIT_CLEAR_RADIUS;
+            if near {
+                particles.explosion(asteroid.position(), LIGHTGRAY);
+            }
+            !near
+        });
+    }
+
+    /// Удар по кораблю от столкновения с объектом в точке `position`: взрыв и потеря жизни.
+    /// Возвращает итоги игры, если жизни закончились.
+    fn hit_ship(&mut self, position: Vec2) -> Option<RunStats> {
+        self.particles.explosion(position, ORANGE);
+        self.sounds.play_explosion();
+        self.shake = Self::SHAKE_INTENSITY;
+
+        // В тренировке корабль просто возвращается на место,
+        if self.practice {
+            self.ship.respawn(self.screen_size);
+            return None;
+        }
+
+        // а в игре корабль теряет жизнь, если удар не принял щит,
+        if !self.ship.hit() {
+            return None;
+        }
+        // Потеря жизни отмечается крупным взрывом самого корабля,
+        // и серия без столкновений начинается заново.
+        self.particles.burst(
+            self.ship.center(),
+            YELLOW,
+            Self::SHIP_EXPLOSION_SIZE,
+            Self::SHIP_EXPLOSION_SPEED,
+            Self::SHIP_EXPLOSION_LIFETIME,
+        );
+        self.reset_streak();
+        self.clear_around_ship();
+        if self.ship.lives() == 0 {
+            // Если жизни закончились, то предлагаем продолжить игру за очки,
+            if self.can_continue() {
+                self.continue_offer = true;
+                return None;
+            }
+            // а если это невозможно - завершаем её.
+            return Some(self.finish());
+        }
+        None
+    }
+
+    /// Предлагается ли сейчас продолжить игру после гибели.
+    pub fn is_continue_offered(&self) -> bool {
+        self.continue_offer
+    }
+
+    /// Цена продолжения игры в очках.
+    fn continue_cost(&self) -> u64 {
+        self.config.continue_cost
+    }
+
+    /// Можно ли продолжить игру после гибели: продолжения не исчерпаны и хватает очков.
+    /// В ежедневном испытании у всех одна попытка, а автопилот не продолжает.
+    fn can_continue(&self) -> bool {
+        self.continues < Self::MAX_CONTINUES
+            && self.score >= self.continue_cost()
+            && self.daily.is_none()
+            && !self.autopilot
+    }
+
+    /// Ожидание решения о продолжении игры. Клавиша старта продолжает игру,
+    /// а клавиша выхода завершает её. При воспроизведении записи
+    /// игра продолжалась, если запись на этом не закончилась.
+    fn update_continue_offer(&mut self, gamepad: &Gamepad) -> Option<RunStats> {
+        self.set_thrust_sound(false);
+        if is_key_pressed(self.key_bindings.quit) {
+            return Some(self.finish());
+        }
+        let accepted = match &self.playback {
+            Some(frames) if frames.as_slice().is_empty() => return Some(self.finish()),
+            Some(_) => true,
+            None => is_key_pressed(self.key_bindings.start) || gamepad.is_start_pressed(),
+        };
+        if accepted {
+            self.continue_game();
+            // Пока игрок решал, время игры стояло, как на паузе.
+            self.resume();
+        }
+        None
+    }
+
+    /// Продолжение игры после гибели: очки списываются, астероиды и преследователи
+    /// исчезают, а корабль возвращается на место с одной жизнью и ненадолго неуязвим.
+    fn continue_game(&mut self) {
+        self.continue_offer = false;
+        self.continues += 1;
+        self.score -= self.continue_cost();
+        self.reset_streak();
+        self.asteroids.retain(|_| false);
+        self.seekers.clear();
+        self.threat = None;
+        self.ship.set_lives(1);
+        self.ship.respawn(self.screen_size);
+    }
+
+    /// Включение или выключение звука двигателя, если его состояние изменилось.
+    fn set_thrust_sound(&mut self, on: bool) {
+        if on == self.thrust_sound {
+            return;
+        }
+        self.thrust_sound = on;
+        if on {
+            self.sounds.start_thrust();
+        } else {
+            self.sounds.stop_thrust();
+        }
+    }
+
+    /// Упругие столкновения астероидов друг с другом за кадр длительностью `elapsed_time`.
+    /// Пары ищутся по сетке, заполненной в этом кадре.
+    #[cfg(feature = "asteroid-collisions")]
+    fn collide_asteroids(&mut self, elapsed_time: f64) {
+        let indices: Vec<usize> = self.asteroids.iter().map(|(index, _)| index).collect();
+        for index in indices {
+            let asteroid = &self.asteroids[index];
+            let offset = Vec2::new(asteroid.radius(), asteroid.radius());
+            let (min, max) = (asteroid.position() - offset, asteroid.position() + offset);
+            for other in self.grid.query_region(min, max) {
+                // Каждую пару обрабатываем один раз.
+                if other <= index {
+                    continue;
+                }
+                let (asteroid, other) = self.asteroids.pair_mut(index, other);
+                asteroid.collide(other, elapsed_time as f32);
+            }
+        }
+    }
+
+    /// Создание нового астероида в свободной ячейке пула.
+    ///
+    /// Астероид не должен появиться поверх другого: тогда они сразу слипаются в скопление,
+    /// от которого трудно увернуться. Поэтому, если новый астероид задевает какой-нибудь
+    /// из существующих, он создаётся заново, но не больше `SPAWN_ATTEMPTS` раз, чтобы
+    /// не задерживать кадр. Возвращает, удалось ли создать астероид.
+    fn spawn_asteroid(&mut self) -> bool {
+        let width = self.screen_size.x;
+        let progression = self.progression();
+        for _ in 0..Self::SPAWN_ATTEMPTS {
+            let asteroid = Asteroid::new(
+                &mut self.rng,
+                self.difficulty,
+                progression,
+                &self.config,
+                width,
+            );
+            let overlaps = self.asteroids.iter().any(|(_, other)| {
+                let gap = asteroid.radius() + other.radius() + Self::SPAWN_GAP;
+                asteroid.position().distance_squared(other.position()) < gap * gap
+            });
+            if !overlaps {
+                self.asteroids.spawn(asteroid);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Установка камеры, смещённой на случайную величину в пределах силы тряски.
+    /// Смещение берётся из генератора macroquad, чтобы не влиять на запись игры.
+    fn set_shake_camera(&self) {
+        let offset = if self.shake > 0.0 {
+            Vec2::new(
+                rand::gen_range(-self.shake, self.shake),
+                rand::gen_range(-self.shake, self.shake),
+            )
+        } else {
+            Vec2::ZERO
+        };
+        let rect = Rect::new(offset.x, offset.y, screen_width(), screen_height());
+        set_camera(&Camera2D::from_display_rect(rect));
+    }
+
+    /// Текущие размеры окна.
+    fn screen_size() -> Vec2 {
+        Vec2::new(screen_width(), screen_height())
+    }
+
+    /// Обработка изменения размеров окна.
+    /// Все объекты переносятся пропорционально новым размерам, так что корабль
+    /// не перескакивает, а астероиды не оказываются за пределами экрана раньше времени.
+    /// Возвращает, изменился ли размер.
+    fn handle_resize(&mut self) -> bool {
+        let screen_size = Self::screen_size();
+        // Свёрнутое окно имеет нулевой размер - в этом случае ничего не трогаем.
+        if screen_size == self.screen_size || screen_size.x <= 0.0 || screen_size.y <= 0.0 {
+            return false;
+        }
+        let scale = screen_size / self.screen_size;
+        self.screen_size = screen_size;
+
+        self.ship.rescale(scale, screen_size);
+        self.starfield.rescale(scale);
+        for asteroid in self.asteroids.iter_mut() {
+            asteroid.rescale(scale);
+        }
+        for bullet in &mut self.bullets {
+            bullet.rescale(scale);
+        }
+        self.particles.rescale(scale);
+        for popup in &mut self.popups {
+            popup.rescale(scale);
+        }
+        for power_up in &mut self.power_ups {
+            power_up.rescale(scale);
+        }
+        for well in &mut self.gravity_wells {
+            well.rescale(scale);
+        }
+        for seeker in &mut self.seekers {
+            seeker.rescale(scale);
+        }
+        true
+    }
+
+    /// Множитель скорости течения времени в мире в кадре длительностью `elapsed_time`.
+    /// Пока действует подобранное замедление или зажато замедление (`slow`) и есть его запас,
+    /// время замедляется, а запас тратится.
+    /// Когда запас кончается, замедление отключается само. В остальное время запас
+    /// восстанавливается, но не больше чем до максимума.
+    fn time_scale(&mut self, slow: bool, elapsed_time: f64) -> f64 {
+        // Подобранное замедление действует само и запас не тратит.
+        if self.slow_motion_timer > 0.0 {
+            self.slow_motion_timer = (self.slow_motion_timer - elapsed_time).max(0.0);
+            return Self::SLOW_TIME_SCALE;
+        }
+        if slow && self.slow_meter > 0.0 {
+            self.slow_meter = (self.slow_meter - elapsed_time).max(0.0);
+            Self::SLOW_TIME_SCALE
+        } else {
+            let recharged = self.slow_meter + Self::SLOW_METER_RECHARGE * elapsed_time;
+            self.slow_meter = recharged.min(Self::SLOW_METER_MAX);
+            1.0
+        }
+    }
+
+    /// Нарастание сложности: со временем, а в режиме волн - с каждой волной.
+    fn progression(&self) -> Progression {
+        if self.waves {
+            Progression::from_remaining(Self::WAVE_INTERVAL_DECAY.powi(self.wave as i32 - 1))
+        } else {
+            Progression::at_time(self.game_time(), &self.config)
+        }
+    }
+
+    /// Интервал появления астероидов. С нарастанием сложности он сокращается,
+    /// плавно приближаясь к минимальному. Уровень сложности растягивает
+    /// или сжимает интервал целиком.
+    fn spawn_interval(&self) -> f64 {
+        let remaining = 1.0 - self.progression().level() as f64;
+        let interval = Self::SPAWN_INTERVAL_FLOOR
+            + (Self::SPAWN_INTERVAL_START - Self::SPAWN_INTERVAL_FLOOR) * remaining;
+        interval * self.difficulty.spawn_interval_scale()
+    }
+
+    /// Число астероидов, появляющихся одновременно. Вначале астероид один, а со временем
+    /// их становится больше, но не больше, чем допускает уровень сложности.
+    /// Вместе с сокращением интервала это постепенно усложняет игру.
+    fn spawn_count(&self) -> u32 {
+        let count = 1 + (self.game_time() / Self::SPAWN_COUNT_PERIOD) as u32;
+        count.min(self.difficulty.max_spawn_count())
+    }
+
+    /// Время в текущей игре.
+    fn game_time(&self) -> f64 {
+        self.time
+    }
+
+    /// Время, прошедшее с начала предыдущего обновления.
+    /// Текущий момент запоминается как начало этого обновления.
+    ///
+    /// Время отсчитывается от начала одного обновления до начала следующего, поэтому
+    /// в длительность кадра входит всё, что происходило между ними: отображение кадра
+    /// и ожидание следующего. Так сумма длительностей кадров совпадает с реальным временем.
+    /// Вызывается один раз за обновление.
+    ///
+    /// После зависания или переключения окна реальное время кадра может быть очень
+    /// большим, поэтому [`Game::update`] ограничивает его `MAX_ELAPSED_TIME` или, если кадр
+    /// длиннее `FOCUS_LOSS_TIME`, ставит игру на паузу. Первый кадр отсчитывается
+    /// от создания игры (или снятия с паузы), так что и он не бывает слишком долгим.
+    fn elapsed_time(&mut self) -> f64 {
+        let now = get_time();
+        let elapsed_time = now - self.last_update;
+        self.last_update = now;
+        elapsed_time
+    }
+
+    /// Отображаем полосу запаса с подписью `label` в правом нижнем углу,
+    /// заполненную на долю `fill`. Полоса с номером `row` отображается над предыдущими.
+    fn draw_meter(label: &str, fill: f32, color: Color, row: u32, theme: &Theme) {
+        const WIDTH: f32 = 150.0;
+        const HEIGHT: f32 = 12.0;
+        const ROW_HEIGHT: f32 = 40.0;
+        let x = screen_width() - WIDTH - 10.0;
+        let y = screen_height() - HEIGHT - 10.0 - ROW_HEIGHT * row as f32;
+        draw_rectangle(x, y, WIDTH * fill, HEIGHT, color);
+        draw_rectangle_lines(x, y, WIDTH, HEIGHT, 2.0, theme.text);
+        draw_ui_text(label, x, y - 4.0, 20.0, theme.text);
+    }
+
+    /// Отображаем текст с лучшим и текущим временем, очками, а также число оставшихся жизней.
+    /// В режиме на время вместо лучшего времени показывается цель, а вместо текущего - остаток.
+    fn draw_time(&self, best_time: f64, lang: Lang, theme: &Theme) {
+        let font_size = 24.0;
+        let text = match self.goal {
+            Some(goal) => format!("{}: {:.2}", lang.text(TextKey::Goal), goal),
+            None => format!("{}: {:.2}", lang.text(TextKey::BestTime), best_time),
+        };
+        let text_size = measure_ui_text(&text, font_size);
+        draw_ui_text(&text, 0.0, screen_height(), font_size, theme.text);
+
+        let time = self.game_time();
+        let (text, color) = match self.goal {
+            // В режиме на время показываем, сколько ещё осталось продержаться.
+            Some(goal) => {
+                let left = (goal - time).max(0.0);
+                let text = format!("{}: {:.2}", lang.text(TextKey::TimeLeft), left);
+                (text, theme.text)
+            }
+            // Если текущее время лучше рекордного, отображаем его цветом рекорда.
+            None => {
+                let text = format!("{}: {:.2}", lang.text(TextKey::YourTime), time);
+                let color = if time > best_time {
+                    theme.record
+                } else {
+                    theme.text
+                };
+                (text, color)
+            }
+        };
+
+        draw_ui_text(
+            &text,
+            0.0,
+            screen_height() - text_size.height,
+            font_size,
+            color,
+        );
+
+        // Жизни показываем значками корабля, а рядом - сколько продолжается серия
+        // без столкновений.
+        let y = screen_height() - 2.0 * text_size.height;
+        let text = format!("{}: ", lang.text(TextKey::Lives));
+        draw_ui_text(&text, 0.0, y, font_size, theme.text);
+        let mut x = measure_ui_text(&text, font_size).width;
+        let icon = Ship::triangle(Vec2::ZERO, 0.0).map(|vertex| vertex * Self::LIFE_ICON_SCALE);
+        let width = icon[2].x - icon[1].x;
+        for _ in 0..self.ship.lives() {
+            let base = Vec2::new(x + width / 2.0, y);
+            let [top, left, right] = icon.map(|vertex| base + vertex);
+            draw_triangle(top, right, left, theme.ship);
+            x += width * 1.5;
+        }
+        let text = format!(
+            " ({}: {:.0})",
+            lang.text(TextKey::Streak),
+            self.streak().floor()
+        );
+        draw_ui_text(&text, x, y, font_size, theme.text);
+
+        let text = format!("{}: {}", lang.text(TextKey::Bombs), self.bombs);
+        draw_ui_text(
+            &text,
+            0.0,
+            screen_height() - 5.0 * text_size.height,
+            font_size,
+            theme.text,
+        );
+
+        if self.waves {
+            let text = format!("{}: {}", lang.text(TextKey::Wave), self.wave);
+            draw_ui_text(
+                &text,
+                0.0,
+                screen_height() - 6.0 * text_size.height,
+                font_size,
+                theme.text,
+            );
+        }
+
+        // Зерно показываем, чтобы игру можно было повторить ключом `--seed` или в настройках.
+        let text = format!("{}: {}", lang.text(TextKey::Seed), self.seed);
+        draw_ui_text(
+            &text,
+            0.0,
+            screen_height() - 3.0 * text_size.height,
+            font_size,
+            theme.text,
+        );
+
+        // Множитель показываем, только пока он действует.
+        let mut text = format!("{}: {}", lang.text(TextKey::Score), self.score);
+        if self.multiplier > 1 {
+            text += &format!(" x{}", self.multiplier);
+        }
+        draw_ui_text(
+            &text,
+            0.0,
+            screen_height() - 4.0 * text_size.height,
+            font_size,
+            theme.text,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Игра без окна с уровнем сложности `difficulty`, настройками по умолчанию
+    /// и постоянным зерном в окне 800 на 600.
+    fn test_game(difficulty: Difficulty) -> Game {
+        Game::simulation(
+            FlightMode::Classic,
+            false,
+            difficulty,
+            GameConfig::default(),
+            42,
+            Vec2::new(800.0, 600.0),
+        )
+    }
+
+    /// Прогон игры без окна: 10 секунд с заранее заданным управлением.
+    /// Корабль плавно качается из стороны в сторону и всё время стреляет.
+    #[test]
+    fn survives_ten_seconds_of_scripted_input() {
+        const FRAME_TIME: f64 = 1.0 / 60.0;
+        const FRAMES: u32 = 600;
+
+        let mut game = test_game(Difficulty::Normal);
+        for frame in 0..FRAMES {
+            let time = frame as f64 * FRAME_TIME;
+            let input = Input {
+                steer: (time * 2.0).sin() as f32,
+                fire: true,
+                ..Input::default()
+            };
+            let frame = FrameInput {
+                input,
+                elapsed_time: FRAME_TIME,
+            };
+            assert!(game.advance(frame).is_none(), "game over at {:.2} s", time);
+        }
+
+        assert!(game.ship.lives() > 0);
+        // Обратный отсчёт закончился, и астероиды успели появиться.
+        assert!(game.game_time() > 0.0);
+        assert!(!game.asteroids.is_empty() || game.dodged > 0);
+    }
+
+    #[test]
+    fn spawn_count_grows_and_is_capped() {
+        let mut game = test_game(Difficulty::Hard);
+        assert_eq!(game.spawn_count(), 1);
+        game.time = Game::SPAWN_COUNT_PERIOD * 1.5;
+        assert_eq!(game.spawn_count(), 2);
+        game.time = Game::SPAWN_COUNT_PERIOD * 100.0;
+        assert_eq!(game.spawn_count(), Difficulty::Hard.max_spawn_count());
+    }
+
+    /// Восстановленная из сохранения игра продолжается так же, как исходная.
+    #[test]
+    fn restored_state_continues_identically() {
+        let mut game = test_game(Difficulty::Normal);
+        let frame = |frame: u32| FrameInput {
+            input: Input {
+                steer: (frame as f32 * 0.05).sin(),
+                fire: true,
+                ..Input::default()
+            },
+            elapsed_time: 1.0 / 60.0,
+        };
+        for index in 0..400 {
+            game.advance(frame(index));
+        }
+
+        let text = serde_json::to_string(&game).unwrap();
+        let mut restored: Game = serde_json::from_str(&text).unwrap();
+        for index in 400..600 {
+            game.advance(frame(index));
+            restored.advance(frame(index));
+        }
+        assert_eq!(restored.ship.position(), game.ship.position());
+        assert_eq!(restored.asteroids.len(), game.asteroids.len());
+        assert_eq!(restored.score, game.score);
+    }
+
+    #[test]
+    fn spawned_asteroids_do_not_overlap() {
+        let mut game = test_game(Difficulty::Hard);
+        // Астероиды создаются в одном ряду над экраном: рано или поздно места не хватит,
+        // и часть попыток закончится ничем.
+        let spawned = (0..20).filter(|_| game.spawn_asteroid()).count();
+        assert!(spawned > 0 && spawned < 20);
+        assert_eq!(game.asteroids.len(), spawned);
+
+        let asteroids: Vec<_> = game
+            .asteroids
+            .iter()
+            .map(|(_, asteroid)| asteroid)
+            .collect();
+        for (index, first) in asteroids.iter().enumerate() {
+            for second in &asteroids[index + 1..] {
+                let distance = first.position().distance(second.position());
+                assert!(distance >= first.radius() + second.radius());
+            }
+        }
+    }
+
+    /// Продолжение стоит очков, убирает астероиды и доступно один раз за игру.
+    #[test]
+    fn continue_costs_score_once_per_run() {
+        let mut game = test_game(Difficulty::Normal);
+        game.spawn_asteroid();
+        game.score = game.continue_cost() + 10;
+        game.ship.set_lives(1);
+        assert!(game.hit_ship(Vec2::ZERO).is_none());
+        assert!(game.continue_offer);
+
+        game.continue_game();
+        assert!(!game.continue_offer);
+        assert_eq!(game.score, 10);
+        assert!(game.asteroids.is_empty());
+        assert_eq!(game.ship.lives(), 1);
+        assert!(game.ship.is_invulnerable());
+
+        // Второй раз продолжить нельзя, даже если очков хватает.
+        game.score = game.continue_cost();
+        assert!(game.hit_ship(Vec2::ZERO).is_some());
+    }
+
+    /// К концу долгой игры астероиды появляются чаще, летят быстрее и в среднем крупнее.
+    #[test]
+    fn difficulty_grows_with_time() {
+        let config = GameConfig::default();
+        let mut game = test_game(Difficulty::Normal);
+        let start = game.spawn_interval();
+        game.time = 600.0;
+        assert!(game.progression().level() > 0.99);
+        assert!(game.spawn_interval() < start / 3.0);
+
+        let late = game.progression();
+        let normal = Difficulty::Normal;
+        assert!(late.max_speed(normal, &config) > Progression::START.max_speed(normal, &config));
+        let mean_radius = |progression: Progression| {
+            let mut rng = Rng::new(7);
+            (0..1000)
+                .map(|_| progression.radius(&mut rng, normal, &config))
+                .sum::<f32>()
+                / 1000.0
+        };
+        assert!(mean_radius(late) > mean_radius(Progression::START) + 10.0);
+    }
+
+    /// Щит принимает один удар вместо жизни, замедление замедляет мир, не тратя запас,
+    /// а множитель удваивает очки.
+    #[test]
+    fn power_ups_apply_their_effects() {
+        let mut game = test_game(Difficulty::Normal);
+        game.collect_power_up(PowerUpKind::Shield);
+        assert!(game.hit_ship(Vec2::ZERO).is_none());
+        assert_eq!(game.ship.lives(), 3);
+        assert_eq!(game.ship.shield_time(), 0.0);
+        game.hit_ship(Vec2::ZERO);
+        assert_eq!(game.ship.lives(), 2);
+
+        game.collect_power_up(PowerUpKind::SlowMotion);
+        assert_eq!(game.time_scale(false, 1.0), Game::SLOW_TIME_SCALE);
+        assert_eq!(game.slow_meter, Game::SLOW_METER_MAX);
+        assert_eq!(game.time_scale(false, 10.0), Game::SLOW_TIME_SCALE);
+        assert_eq!(game.time_scale(false, 1.0), 1.0);
+
+        game.collect_power_up(PowerUpKind::ScoreBoost);
+        game.register_kill();
+        assert_eq!(game.score, Game::KILL_SCORE * Game::SCORE_BOOST);
+    }
+
+    /// Удар отнимает жизнь и разрушает астероиды рядом с кораблём, но не дальние.
+    #[test]
+    fn hit_clears_nearby_asteroids() {
+        let mut game = test_game(Difficulty::Normal);
+        let center = game.ship.center();
+        for offset in [Vec2::new(60.0, 0.0), Vec2::new(0.0, -400.0)] {
+            let asteroid = Asteroid::with_motion(
+                &mut game.rng,
+                game.difficulty,
+                &game.config,
+                center + offset,
+                Vec2::ZERO,
+            );
+            game.asteroids.spawn(asteroid);
+        }
+
+        assert!(game.hit_ship(center).is_none());
+        assert_eq!(game.ship.lives(), 2);
+        assert!(game.ship.is_invulnerable());
+        let remaining: Vec<_> = game.asteroids.iter().map(|(_, a)| a.position()).collect();
+        assert_eq!(remaining, [center + Vec2::new(0.0, -400.0)]);
+    }
+
+    /// Очки за серию без столкновений растут с её длительностью,
+    /// а после удара серия начинается заново.
+    #[test]
+    fn streak_bonus_escalates_and_resets_on_hit() {
+        let mut game = test_game(Difficulty::Normal);
+        game.time = Game::STREAK_INTERVAL * 2.5;
+        game.update_streak();
+        assert_eq!(game.score, Game::STREAK_BONUS * 3);
+        // Уже вознаграждённые интервалы повторно не учитываются.
+        game.update_streak();
+        assert_eq!(game.score, Game::STREAK_BONUS * 3);
+
+        game.hit_ship(Vec2::ZERO);
+        assert_eq!(game.streak(), 0.0);
+        game.time += Game::STREAK_INTERVAL;
+        game.update_streak();
+        assert_eq!(game.score, Game::STREAK_BONUS * 4);
+    }
+
+    /// Очень долгий кадр продвигает игру так же, как кадр длительностью `MAX_ELAPSED_TIME`,
+    /// а астероид, долетающий до корабля за это время, не пролетает сквозь него.
+    #[test]
+    fn long_frame_is_clamped_without_tunnelling() {
+        let run = |elapsed_time: f64| {
+            let mut game = test_game(Difficulty::Normal);
+            game.countdown = 0.0;
+            // Далёкий астероид в стороне и быстрый астероид прямо над кораблём.
+            let center = game.ship.center();
+            for (position, speed) in [
+                (Vec2::new(100.0, 100.0), Vec2::new(50.0, 100.0)),
+                (center - Vec2::new(0.0, 150.0), Vec2::new(0.0, 3000.0)),
+            ] {
+                let asteroid = Asteroid::with_motion(
+                    &mut game.rng,
+                    game.difficulty,
+                    &game.config,
+                    position,
+                    speed,
+                );
+                game.asteroids.spawn(asteroid);
+            }
+            let frame = FrameInput {
+                input: Input::default(),
+                elapsed_time,
+            };
+            assert!(game.advance(frame).is_none());
+            let positions: Vec<_> = game.asteroids.iter().map(|(_, a)| a.position()).collect();
+            (
+                game.time,
+                game.ship.position(),
+                game.ship.lives(),
+                positions,
+            )
+        };
+
+        let long = run(2.0);
+        assert_eq!(long, run(Game::MAX_ELAPSED_TIME));
+        let (time, _, lives, asteroids) = long;
+        assert_eq!(time, Game::MAX_ELAPSED_TIME);
+        // Быстрый астероид задел корабль и разрушился, а далёкий остался.
+        assert_eq!(lives, 2);
+        assert_eq!(asteroids.len(), 1);
+        assert!(asteroids[0].y < 120.0);
+    }
+
+    /// Игра при 30 и при 120 кадрах в секунду идёт одинаково: мир обновляется
+    /// шагами постоянной длительности, сколько бы их ни пришлось на кадр.
+    #[test]
+    fn frame_rate_does_not_change_gameplay() {
+        let run = |steps_per_frame: u32| {
+            let mut game = test_game(Difficulty::Normal);
+            game.countdown = 0.0;
+            let frame = FrameInput {
+                input: Input {
+                    steer: 0.5,
+                    ..Input::default()
+                },
+                elapsed_time: Game::STEP * steps_per_frame as f64,
+            };
+            for _ in 0..(480 / steps_per_frame) {
+                if game.advance(frame).is_some() {
+                    break;
+                }
+            }
+            (game.ship.position(), game.asteroids.len(), game.score)
+        };
+        assert_eq!(run(4), run(1));
+    }
+
+    #[test]
+    fn fragments_are_capped_by_max_asteroids() {
+        let mut game = test_game(Difficulty::Normal);
+        game.config.max_asteroids = 3;
+        let config = game.config;
+        let mut rng = Rng::new(1);
+        let asteroid = |rng: &mut Rng| {
+            Asteroid::new(rng, Difficulty::Normal, Progression::START, &config, 800.0)
+        };
+        game.asteroids.spawn(asteroid(&mut rng));
+        game.asteroids.spawn(asteroid(&mut rng));
+        game.spawn_fragments([asteroid(&mut rng), asteroid(&mut rng)]);
+        assert_eq!(game.asteroids.len(), 3);
+    }
+}
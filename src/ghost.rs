@@ -0,0 +1,175 @@
+//! «Призрак» лучшей игры: полупрозрачный корабль, повторяющий её движение.
+//!
+//! Призрак имеет смысл только в играх с одинаковым зерном, где астероиды те же,
+//! поэтому он сохраняется вместе с зерном и показывается только в играх с ним.
+
+use crate::ship::Ship;
+use crate::storage;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Файл, в котором хранится призрак лучшей игры.
+const GHOST_FILE: &str = "ghost.txt";
+
+/// Положение корабля в момент игры.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct Sample {
+    /// Время игры.
+    time: f64,
+    /// Положение корабля.
+    position: Vec2,
+    /// Угол поворота корабля.
+    rotation: f32,
+}
+
+/// Путь корабля за одну игру.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Ghost {
+    /// Зерно генератора случайных чисел игры.
+    seed: u64,
+    /// Положения корабля по возрастанию времени.
+    samples: Vec<Sample>,
+}
+
+impl Ghost {
+    /// Интервал между запоминаемыми положениями. Между ними положение интерполируется.
+    const INTERVAL: f64 = 1.0 / 30.0;
+    /// Если между соседними положениями корабль сместился дальше, значит, он перенёсся
+    /// через край окна, и интерполировать между ними нельзя.
+    const MAX_JUMP: f32 = 100.0;
+    /// Непрозрачность призрака.
+    const OPACITY: f32 = 0.3;
+
+    /// Создание пустого пути для игры с зерном `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Зерно игры, в которой записан путь.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Продолжительность записанной игры.
+    pub fn time(&self) -> f64 {
+        self.samples.last().map_or(0.0, |sample| sample.time)
+    }
+
+    /// Запоминание положения `position` и поворота `rotation` корабля в момент `time`,
+    /// если с предыдущего запомненного положения прошло не меньше `INTERVAL`.
+    pub fn record(&mut self, time: f64, position: Vec2, rotation: f32) {
+        let due = self
+            .samples
+            .last()
+            .is_none_or(|last| time - last.time >= Self::INTERVAL);
+        if due {
+            self.samples.push(Sample {
+                time,
+                position,
+                rotation,
+            });
+        }
+    }
+
+    /// Положение и поворот корабля в момент `time`. После конца записи призрак
+    /// остаётся в последнем положении.
+    fn pose_at(&self, time: f64) -> Option<(Vec2, f32)> {
+        let index = self.samples.partition_point(|sample| sample.time <= time);
+        let (before, after) = match (index.checked_sub(1), self.samples.get(index)) {
+            (Some(before), Some(after)) => (self.samples[before], *after),
+            (Some(before), None) => return Some(self.samples[before].pose()),
+            (None, Some(after)) => return Some(after.pose()),
+            (None, None) => return None,
+        };
+        if before.position.distance(after.position) > Self::MAX_JUMP {
+            return Some(before.pose());
+        }
+        let alpha = ((time - before.time) / (after.time - before.time)) as f32;
+        let position = before.position.lerp(after.position, alpha);
+        let rotation = before.rotation + (after.rotation - before.rotation) * alpha;
+        Some((position, rotation))
+    }
+
+    /// Отображение призрака в момент игры `time` полупрозрачным кораблём цвета `color`.
+    pub fn draw(&self, time: f64, color: Color) {
+        if let Some((position, rotation)) = self.pose_at(time) {
+            let [top, left, right] = Ship::triangle(position, rotation);
+            let color = Color::new(color.r, color.g, color.b, Self::OPACITY);
+            draw_triangle(top, right, left, color);
+        }
+    }
+
+    /// Сохранение пути в файл. Ошибка записи не прерывает игру.
+    ///
+    /// Первая строка файла содержит зерно, а каждая следующая - одно положение:
+    /// время, координаты и поворот корабля.
+    pub fn save(&self) {
+        let mut text = format!("seed {}\n", self.seed);
+        for sample in &self.samples {
+            text += &format!(
+                "{} {} {} {}\n",
+                sample.time, sample.position.x, sample.position.y, sample.rotation
+            );
+        }
+        if let Err(err) = storage::write(GHOST_FILE, &text) {
+            warn!("Failed to save ghost: {}", err);
+        }
+    }
+
+    /// Загрузка пути из файла. Если файла нет или он повреждён, то призрака нет.
+    pub fn load() -> Option<Self> {
+        let text = storage::read(GHOST_FILE)?;
+        let ghost = Self::parse(&text);
+        if ghost.is_none() {
+            warn!("Failed to parse ghost file {}", GHOST_FILE);
+        }
+        ghost
+    }
+
+    /// Разбор пути из текста в формате, описанном в [`Ghost::save`].
+    fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        let seed = lines.next()?.strip_prefix("seed ")?.parse().ok()?;
+        let mut ghost = Self::new(seed);
+        for line in lines {
+            let mut fields = line.split_whitespace().map(str::parse::<f64>);
+            let mut next = || fields.next()?.ok();
+            let (time, x, y, rotation) = (next()?, next()?, next()?, next()?);
+            ghost.samples.push(Sample {
+                time,
+                position: Vec2::new(x as f32, y as f32),
+                rotation: rotation as f32,
+            });
+        }
+        Some(ghost)
+    }
+}
+
+impl Sample {
+    /// Положение и поворот.
+    fn pose(self) -> (Vec2, f32) {
+        (self.position, self.rotation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pose_interpolates_and_stops_at_end() {
+        let mut ghost = Ghost::new(42);
+        ghost.record(0.0, Vec2::new(0.0, 0.0), 0.0);
+        ghost.record(0.01, Vec2::new(5.0, 0.0), 0.0); // Слишком рано - пропускается.
+        ghost.record(0.1, Vec2::new(10.0, 0.0), 1.0);
+        assert_eq!(ghost.samples.len(), 2);
+
+        assert_eq!(ghost.pose_at(0.05), Some((Vec2::new(5.0, 0.0), 0.5)));
+        // Текущая игра длится дольше записанной: призрак стоит в последнем положении.
+        assert_eq!(ghost.pose_at(100.0), Some((Vec2::new(10.0, 0.0), 1.0)));
+        assert_eq!(Ghost::new(42).pose_at(1.0), None);
+    }
+}
@@ -0,0 +1,88 @@
+//! Гравитационные колодцы, притягивающие астероиды.
+
+use crate::rng::Rng;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Гравитационный колодец. Движется навстречу кораблю, как астероиды,
+/// и притягивает к себе астероиды в пределах своего радиуса.
+#[derive(Serialize, Deserialize)]
+pub struct GravityWell {
+    position: Vec2,
+    /// Положение перед последним шагом игрового мира, для плавного отображения между шагами.
+    prev_position: Vec2,
+    /// Сила притяжения: ускорение на расстоянии одного пикселя от центра.
+    /// Ускорение обратно пропорционально расстоянию.
+    strength: f32,
+    /// Радиус, за пределами которого колодец не притягивает.
+    radius: f32,
+    /// Время с появления колодца в секундах (для пульсации).
+    age: f32,
+}
+
+impl GravityWell {
+    // Параметры колодцев.
+    const MIN_RADIUS: f32 = 150.0;
+    const MAX_RADIUS: f32 = 250.0;
+    const MIN_STRENGTH: f32 = 15_000.0;
+    const MAX_STRENGTH: f32 = 30_000.0;
+    /// Ближе этого расстояния к центру притяжение не растёт, иначе астероиды,
+    /// пролетающие через центр, разгонялись бы до огромной скорости.
+    const MIN_DISTANCE: f32 = 40.0;
+    /// Число пульсаций в секунду.
+    const PULSE_RATE: f32 = 1.5;
+
+    /// Создание колодца со случайными размером и силой над экраном шириной `width`.
+    pub fn new(rng: &mut Rng, width: f32) -> Self {
+        let radius = rng.gen_range(Self::MIN_RADIUS, Self::MAX_RADIUS);
+        let position = Vec2::new(rng.gen_range(0.0, width), -radius);
+        Self {
+            position,
+            prev_position: position,
+            strength: rng.gen_range(Self::MIN_STRENGTH, Self::MAX_STRENGTH),
+            radius,
+            age: 0.0,
+        }
+    }
+
+    /// Ускорение, которое колодец сообщает телу в точке `point`.
+    pub fn acceleration(&self, point: Vec2) -> Vec2 {
+        let delta = self.position - point;
+        let distance = delta.length();
+        if distance >= self.radius || distance <= f32::EPSILON {
+            return Vec2::ZERO;
+        }
+        delta / distance * (self.strength / distance.max(Self::MIN_DISTANCE))
+    }
+
+    /// Обновление состояния колодца. Колодец движется навстречу кораблю со скоростью `ship_speed`.
+    pub fn update(&mut self, elapsed_time: f64, ship_speed: f32) {
+        let elapsed_time = elapsed_time as f32;
+        self.prev_position = self.position;
+        self.position.y += ship_speed * elapsed_time;
+        self.age += elapsed_time;
+    }
+
+    /// Ушёл ли колодец целиком за нижний край экрана высотой `height`.
+    pub fn out_of_bounds(&self, height: f32) -> bool {
+        self.position.y - self.radius > height
+    }
+
+    /// Перенос колодца в окно изменившегося размера.
+    pub fn rescale(&mut self, scale: Vec2) {
+        self.position *= scale;
+        self.prev_position *= scale;
+    }
+
+    /// Отображение колодца полупрозрачными кругами: границей притяжения
+    /// и пульсирующим ядром. `alpha` - доля пути от предыдущего положения к текущему.
+    pub fn draw(&self, alpha: f32) {
+        let position = self.prev_position.lerp(self.position, alpha);
+        let pulse = (self.age * Self::PULSE_RATE * std::f32::consts::TAU).sin() * 0.5 + 0.5;
+        let (x, y) = (position.x, position.y);
+        draw_circle(x, y, self.radius, Color::new(0.6, 0.3, 1.0, 0.08));
+        draw_circle_lines(x, y, self.radius, 1.0, Color::new(0.6, 0.3, 1.0, 0.3));
+        let core = Self::MIN_DISTANCE * (0.8 + 0.4 * pulse);
+        draw_circle(x, y, core, Color::new(0.6, 0.3, 1.0, 0.2 + 0.2 * pulse));
+    }
+}
@@ -0,0 +1,527 @@
+//! Ввод: назначение клавиш и геймпады.
+
+use crate::lang::TextKey;
+use crate::storage;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Файл с назначением клавиш управления.
+const KEY_BINDINGS_FILE: &str = "controls.cfg";
+
+/// Назначение клавиш управления.
+#[derive(Clone, Copy)]
+pub struct KeyBindings {
+    /// Движение (или поворот) влево.
+    pub left: KeyCode,
+    /// Движение (или поворот) вправо.
+    pub right: KeyCode,
+    /// Тяга в режиме свободного полёта, форсаж или движение вверх в классическом режиме.
+    pub thrust: KeyCode,
+    /// Движение вниз в классическом режиме с движением по вертикали.
+    pub down: KeyCode,
+    /// Выстрел.
+    pub fire: KeyCode,
+    /// Замедление времени (пока клавиша зажата).
+    pub slow: KeyCode,
+    /// Бомба, уничтожающая астероиды вокруг корабля.
+    pub bomb: KeyCode,
+    /// Пауза.
+    pub pause: KeyCode,
+    /// Выход из игры в меню.
+    pub quit: KeyCode,
+    /// Запуск игры из меню.
+    pub start: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            left: KeyCode::A,
+            right: KeyCode::D,
+            thrust: KeyCode::W,
+            down: KeyCode::S,
+            fire: KeyCode::Space,
+            slow: KeyCode::LeftShift,
+            bomb: KeyCode::B,
+            pause: KeyCode::P,
+            quit: KeyCode::Escape,
+            start: KeyCode::Enter,
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Загрузка назначения клавиш из файла настроек.
+    ///
+    /// Файл состоит из строк вида `left = Q`. Клавиши, не упомянутые в файле,
+    /// а также строки, которые не удалось разобрать, остаются по умолчанию.
+    pub fn load() -> Self {
+        let mut bindings = Self::default();
+        let text = match storage::read(KEY_BINDINGS_FILE) {
+            Some(text) => text,
+            None => return bindings, // Файла нет - используем клавиши по умолчанию.
+        };
+
+        for line in text.lines().map(str::trim) {
+            // Пропускаем пустые строки и комментарии.
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // Назначаем клавишу действию, если и то, и другое удалось распознать.
+            let assigned = line.split_once('=').and_then(|(action, key)| {
+                let key = parse_key(key.trim())?;
+                *bindings.action_mut(action.trim())? = key;
+                Some(())
+            });
+
+            if assigned.is_none() {
+                warn!("Failed to parse key binding: {}", line);
+            }
+        }
+
+        bindings
+    }
+
+    /// Названия действий и назначенные им клавиши (для подсказок на экране).
+    pub fn actions(&self) -> [(TextKey, KeyCode); 10] {
+        [
+            (TextKey::ActionLeft, self.left),
+            (TextKey::ActionRight, self.right),
+            (TextKey::ActionThrust, self.thrust),
+            (TextKey::ActionDown, self.down),
+            (TextKey::ActionFire, self.fire),
+            (TextKey::ActionSlow, self.slow),
+            (TextKey::ActionBomb, self.bomb),
+            (TextKey::ActionPause, self.pause),
+            (TextKey::ActionQuit, self.quit),
+            (TextKey::ActionStart, self.start),
+        ]
+    }
+
+    /// Клавиша, назначенная действию с именем `action`.
+    fn action_mut(&mut self, action: &str) -> Option<&mut KeyCode> {
+        match action {
+            "left" => Some(&mut self.left),
+            "right" => Some(&mut self.right),
+            "thrust" => Some(&mut self.thrust),
+            "down" => Some(&mut self.down),
+            "fire" => Some(&mut self.fire),
+            "slow" => Some(&mut self.slow),
+            "bomb" => Some(&mut self.bomb),
+            "pause" => Some(&mut self.pause),
+            "quit" => Some(&mut self.quit),
+            "start" => Some(&mut self.start),
+            _ => None,
+        }
+    }
+}
+
+/// Разбор названия клавиши, совпадающего с названием варианта `KeyCode`.
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let key = match name {
+        "A" => KeyCode::A,
+        "B" => KeyCode::B,
+        "C" => KeyCode::C,
+        "D" => KeyCode::D,
+        "E" => KeyCode::E,
+        "F" => KeyCode::F,
+        "G" => KeyCode::G,
+        "H" => KeyCode::H,
+        "I" => KeyCode::I,
+        "J" => KeyCode::J,
+        "K" => KeyCode::K,
+        "L" => KeyCode::L,
+        "M" => KeyCode::M,
+        "N" => KeyCode::N,
+        "O" => KeyCode::O,
+        "P" => KeyCode::P,
+        "Q" => KeyCode::Q,
+        "R" => KeyCode::R,
+        "S" => KeyCode::S,
+        "T" => KeyCode::T,
+        "U" => KeyCode::U,
+        "V" => KeyCode::V,
+        "W" => KeyCode::W,
+        "X" => KeyCode::X,
+        "Y" => KeyCode::Y,
+        "Z" => KeyCode::Z,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Space" => KeyCode::Space,
+        "Enter" => KeyCode::Enter,
+        "Escape" => KeyCode::Escape,
+        "Tab" => KeyCode::Tab,
+        "Backspace" => KeyCode::Backspace,
+        "LeftShift" => KeyCode::LeftShift,
+        "RightShift" => KeyCode::RightShift,
+        "LeftControl" => KeyCode::LeftControl,
+        "RightControl" => KeyCode::RightControl,
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Кривая отклика аналогового управления.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Curve {
+    /// Отклик пропорционален отклонению.
+    Linear,
+    /// Отклик пропорционален квадрату отклонения: точнее при малых отклонениях.
+    Quadratic,
+}
+
+impl Curve {
+    /// Следующая кривая (для переключения в настройках).
+    pub fn next(self) -> Self {
+        match self {
+            Curve::Linear => Curve::Quadratic,
+            Curve::Quadratic => Curve::Linear,
+        }
+    }
+
+    /// Название кривой для отображения.
+    pub fn name(self) -> TextKey {
+        match self {
+            Curve::Linear => TextKey::CurveLinear,
+            Curve::Quadratic => TextKey::CurveQuadratic,
+        }
+    }
+
+    /// Название кривой в файле настроек.
+    pub fn key(self) -> &'static str {
+        match self {
+            Curve::Linear => "linear",
+            Curve::Quadratic => "quadratic",
+        }
+    }
+
+    /// Кривая по её названию в файле, см. [`Curve::key`].
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "linear" => Some(Curve::Linear),
+            "quadratic" => Some(Curve::Quadratic),
+            _ => None,
+        }
+    }
+}
+
+/// Обработка аналогового отклонения `raw` от -1 до 1: отклонения в пределах мёртвой зоны
+/// `dead_zone` не учитываются, а остальные растягиваются на весь диапазон и проходят
+/// через кривую отклика `curve`. Знак отклонения сохраняется.
+pub fn shape_axis(raw: f32, dead_zone: f32, curve: Curve) -> f32 {
+    let magnitude = raw.abs().min(1.0);
+    if magnitude <= dead_zone {
+        return 0.0;
+    }
+    let magnitude = (magnitude - dead_zone) / (1.0 - dead_zone);
+    let shaped = match curve {
+        Curve::Linear => magnitude,
+        Curve::Quadratic => magnitude * magnitude,
+    };
+    shaped.copysign(raw)
+}
+
+/// Настройки аналогового управления.
+#[derive(Clone, Copy)]
+pub struct AxisResponse {
+    /// Мёртвая зона: доля хода стика у центра, которая не учитывается.
+    pub dead_zone: f32,
+    /// Кривая отклика.
+    pub curve: Curve,
+}
+
+/// Управление кораблём в одном кадре.
+///
+/// Собирается с клавиатуры и геймпада один раз за кадр, поэтому корабль не зависит
+/// от источника управления: им может быть и запись игры.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Input {
+    /// Отклонение от -1 (влево) до 1 (вправо).
+    pub steer: f32,
+    /// Включена ли тяга (в режиме свободного полёта), форсаж или движение вверх
+    /// (в классическом режиме без движения по вертикали и с ним).
+    pub thrust: bool,
+    /// Зажато ли движение вниз (в классическом режиме с движением по вертикали).
+    pub descend: bool,
+    /// Нажат ли выстрел в этом кадре.
+    pub fire: bool,
+    /// Зажато ли замедление времени.
+    pub slow: bool,
+    /// Нажата ли бомба в этом кадре.
+    pub bomb: bool,
+    /// Координата курсора мыши по горизонтали, если корабль управляется мышью.
+    pub target: Option<f32>,
+}
+
+impl Input {
+    /// Чтение управления с клавиатуры, геймпада и сенсорного экрана, а если установлен `mouse_steering` -
+    /// то и положения курсора мыши. Отклонение стика обрабатывается согласно `response`.
+    pub fn read(
+        keys: &KeyBindings,
+        gamepad: &Gamepad,
+        mouse_steering: bool,
+        response: AxisResponse,
+    ) -> Self {
+        // Клавиатура даёт полное отклонение, а аналоговый стик геймпада - пропорциональное.
+        let mut steer = shape_axis(gamepad.steer(), response.dead_zone, response.curve);
+        if is_key_down(keys.left) {
+            steer -= 1.0;
+        }
+        if is_key_down(keys.right) {
+            steer += 1.0;
+        }
+        // Касание левой или правой половины экрана уводит корабль в эту сторону.
+        for touch in touches() {
+            if touch.phase == TouchPhase::Ended || touch.phase == TouchPhase::Cancelled {
+                continue;
+            }
+            if touch.position.x < screen_width() / 2.0 {
+                steer -= 1.0;
+            } else {
+                steer += 1.0;
+            }
+        }
+
+        Self {
+            steer: steer.clamp(-1.0, 1.0),
+            thrust: is_key_down(keys.thrust) || gamepad.is_thrust_down(),
+            descend: is_key_down(keys.down) || gamepad.is_descend_down(),
+            fire: is_key_pressed(keys.fire) || gamepad.is_fire_pressed(),
+            slow: is_key_down(keys.slow) || gamepad.is_slow_down(),
+            bomb: is_key_pressed(keys.bomb) || gamepad.is_bomb_pressed(),
+            target: mouse_steering.then(|| mouse_position().0),
+        }
+    }
+}
+
+/// Стрелки, нажатые в этом кадре, для перемещения по меню.
+#[derive(Clone, Copy, Default)]
+pub struct Arrows {
+    /// Вверх.
+    pub up: bool,
+    /// Вниз.
+    pub down: bool,
+    /// Влево.
+    pub left: bool,
+    /// Вправо.
+    pub right: bool,
+}
+
+impl Arrows {
+    /// Чтение стрелок клавиатуры и крестовины геймпада.
+    pub fn read(gamepad: &Gamepad) -> Self {
+        let dpad = gamepad.arrows();
+        Self {
+            up: is_key_pressed(KeyCode::Up) || dpad.up,
+            down: is_key_pressed(KeyCode::Down) || dpad.down,
+            left: is_key_pressed(KeyCode::Left) || dpad.left,
+            right: is_key_pressed(KeyCode::Right) || dpad.right,
+        }
+    }
+}
+
+/// Ввод с геймпада.
+///
+/// Геймпады опрашиваются только при сборке с флагом `gamepad`. Без него, а также
+/// если ни один геймпад не подключён, все методы возвращают нейтральные значения,
+/// и игра управляется только с клавиатуры.
+#[derive(Default)]
+pub struct Gamepad {
+    /// Подсистема работы с геймпадами. Отсутствует, если её не удалось инициализировать.
+    #[cfg(feature = "gamepad")]
+    gilrs: Option<gilrs::Gilrs>,
+    /// Кнопки, нажатые с момента предыдущего опроса.
+    #[cfg(feature = "gamepad")]
+    pressed: Vec<gilrs::Button>,
+}
+
+#[cfg(feature = "gamepad")]
+impl Gamepad {
+    /// Инициализация работы с геймпадами.
+    pub fn new() -> Self {
+        let gilrs = gilrs::Gilrs::new()
+            .map_err(|err| warn!("Failed to initialize gamepads: {}", err))
+            .ok();
+        Self {
+            gilrs,
+            pressed: Vec::new(),
+        }
+    }
+
+    /// Опрос событий геймпадов. Вызывается один раз за кадр.
+    pub fn poll(&mut self) {
+        self.pressed.clear();
+        if let Some(gilrs) = &mut self.gilrs {
+            while let Some(event) = gilrs.next_event() {
+                if let gilrs::EventType::ButtonPressed(button, _) = event.event {
+                    self.pressed.push(button);
+                }
+            }
+        }
+    }
+
+    /// Первый подключённый геймпад.
+    fn active(&self) -> Option<gilrs::Gamepad<'_>> {
+        let gilrs = self.gilrs.as_ref()?;
+        let (_, gamepad) = gilrs
+            .gamepads()
+            .find(|(_, gamepad)| gamepad.is_connected())?;
+        Some(gamepad)
+    }
+
+    /// Горизонтальное отклонение от -1 (влево) до 1 (вправо) по левому стику или крестовине.
+    /// Мёртвая зона и кривая отклика здесь не учитываются, см. [`shape_axis`].
+    pub fn steer(&self) -> f32 {
+        let gamepad = match self.active() {
+            Some(gamepad) => gamepad,
+            None => return 0.0,
+        };
+
+        if gamepad.is_pressed(gilrs::Button::DPadLeft) {
+            return -1.0;
+        }
+        if gamepad.is_pressed(gilrs::Button::DPadRight) {
+            return 1.0;
+        }
+
+        gamepad.value(gilrs::Axis::LeftStickX)
+    }
+
+    /// Удерживается ли тяга: стик или крестовина вверх.
+    pub fn is_thrust_down(&self) -> bool {
+        self.active().is_some_and(|gamepad| {
+            gamepad.is_pressed(gilrs::Button::DPadUp)
+                || gamepad.value(gilrs::Axis::LeftStickY) > 0.5
+        })
+    }
+
+    /// Удерживается ли движение вниз: стик или крестовина вниз.
+    pub fn is_descend_down(&self) -> bool {
+        self.active().is_some_and(|gamepad| {
+            gamepad.is_pressed(gilrs::Button::DPadDown)
+                || gamepad.value(gilrs::Axis::LeftStickY) < -0.5
+        })
+    }
+
+    /// Удерживается ли замедление времени: левый или правый курок.
+    pub fn is_slow_down(&self) -> bool {
+        self.active().is_some_and(|gamepad| {
+            gamepad.is_pressed(gilrs::Button::LeftTrigger2)
+                || gamepad.is_pressed(gilrs::Button::RightTrigger2)
+        })
+    }
+
+    /// Нажата ли кнопка запуска игры (нижняя кнопка или Start).
+    pub fn is_start_pressed(&self) -> bool {
+        self.pressed
+            .iter()
+            .any(|&button| button == gilrs::Button::South || button == gilrs::Button::Start)
+    }
+
+    /// Нажата ли кнопка паузы (Start).
+    pub fn is_pause_pressed(&self) -> bool {
+        self.pressed.contains(&gilrs::Button::Start)
+    }
+
+    /// Нажата ли кнопка выстрела (нижняя кнопка).
+    pub fn is_fire_pressed(&self) -> bool {
+        self.pressed.contains(&gilrs::Button::South)
+    }
+
+    /// Нажата ли кнопка бомбы (верхняя кнопка).
+    pub fn is_bomb_pressed(&self) -> bool {
+        self.pressed.contains(&gilrs::Button::North)
+    }
+
+    /// Нажатые направления крестовины, для перемещения по меню.
+    pub fn arrows(&self) -> Arrows {
+        Arrows {
+            up: self.pressed.contains(&gilrs::Button::DPadUp),
+            down: self.pressed.contains(&gilrs::Button::DPadDown),
+            left: self.pressed.contains(&gilrs::Button::DPadLeft),
+            right: self.pressed.contains(&gilrs::Button::DPadRight),
+        }
+    }
+}
+
+#[cfg(not(feature = "gamepad"))]
+impl Gamepad {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn poll(&mut self) {}
+
+    pub fn steer(&self) -> f32 {
+        0.0
+    }
+
+    pub fn is_thrust_down(&self) -> bool {
+        false
+    }
+
+    pub fn is_descend_down(&self) -> bool {
+        false
+    }
+
+    pub fn is_slow_down(&self) -> bool {
+        false
+    }
+
+    pub fn is_start_pressed(&self) -> bool {
+        false
+    }
+
+    pub fn is_pause_pressed(&self) -> bool {
+        false
+    }
+
+    pub fn is_fire_pressed(&self) -> bool {
+        false
+    }
+
+    pub fn is_bomb_pressed(&self) -> bool {
+        false
+    }
+
+    pub fn arrows(&self) -> Arrows {
+        Arrows::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dead_zone_is_ignored() {
+        for curve in [Curve::Linear, Curve::Quadratic] {
+            assert_eq!(shape_axis(0.0, 0.2, curve), 0.0);
+            assert_eq!(shape_axis(0.2, 0.2, curve), 0.0);
+            assert_eq!(shape_axis(-0.1, 0.2, curve), 0.0);
+            // За мёртвой зоной отклонение растягивается на весь диапазон.
+            assert_eq!(shape_axis(1.0, 0.2, curve), 1.0);
+            assert_eq!(shape_axis(-1.0, 0.2, curve), -1.0);
+            assert_eq!(shape_axis(1.5, 0.2, curve), 1.0);
+        }
+        assert!((shape_axis(0.6, 0.2, Curve::Linear) - 0.5).abs() < 1e-6);
+        assert!((shape_axis(-0.6, 0.2, Curve::Quadratic) + 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn curves_are_monotonic() {
+        for curve in [Curve::Linear, Curve::Quadratic] {
+            for dead_zone in [0.0, 0.15, 0.5] {
+                let mut previous = shape_axis(-1.0, dead_zone, curve);
+                for step in -99..=100 {
+                    let value = shape_axis(step as f32 / 100.0, dead_zone, curve);
+                    assert!(value >= previous, "{} after {}", value, previous);
+                    previous = value;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Индикаторы управления внизу экрана, показывающие зрителям записи или трансляции,
+//! что нажимает игрок.
+
+use crate::input::Input;
+use crate::theme::Theme;
+use macroquad::prelude::*;
+
+/// Индикаторы влево, вправо, форсажа и выстрела. Горят, пока управление задействовано.
+/// По умолчанию скрыты, переключаются клавишей I.
+#[derive(Default)]
+pub struct InputDisplay {
+    /// Показаны ли индикаторы.
+    visible: bool,
+    /// Управление в последнем кадре.
+    input: Input,
+    /// Оставшееся время свечения индикатора выстрела.
+    fire_glow: f32,
+}
+
+impl InputDisplay {
+    // Размеры индикаторов на экране.
+    const KEY_SIZE: f32 = 36.0;
+    const GAP: f32 = 6.0;
+    const MARGIN: f32 = 10.0;
+    /// Выстрел нажимается на один кадр, поэтому его индикатор горит ещё столько секунд,
+    /// иначе нажатие не разглядеть.
+    const FIRE_GLOW: f32 = 0.15;
+
+    /// Переключение индикаторов.
+    pub fn update(&mut self) {
+        if is_key_pressed(KeyCode::I) {
+            self.visible = !self.visible;
+        }
+    }
+
+    /// Запоминание управления `input` в кадре длительностью `frame_time`.
+    pub fn show(&mut self, input: Input, frame_time: f32) {
+        self.input = input;
+        self.fire_glow = if input.fire {
+            Self::FIRE_GLOW
+        } else {
+            (self.fire_glow - frame_time).max(0.0)
+        };
+    }
+
+    /// Отображение индикаторов в ряд посередине нижнего края экрана, между временем
+    /// и полосами запаса, если они включены. Яркость индикатора поворота соответствует
+    /// отклонению стика.
+    pub fn draw(&self, theme: &Theme) {
+        if !self.visible {
+            return;
+        }
+
+        let levels = [
+            (-self.input.steer).max(0.0),
+            self.input.steer.max(0.0),
+            if self.input.thrust { 1.0 } else { 0.0 },
+            self.fire_glow / Self::FIRE_GLOW,
+        ];
+        let width = levels.len() as f32 * (Self::KEY_SIZE + Self::GAP) - Self::GAP;
+        let x = (screen_width() - width) / 2.0;
+        let y = screen_height() - Self::KEY_SIZE - Self::MARGIN;
+
+        for (index, level) in levels.into_iter().enumerate() {
+            let x = x + index as f32 * (Self::KEY_SIZE + Self::GAP);
+            let mut fill = theme.selection;
+            fill.a = 0.7 * level;
+            draw_rectangle(x, y, Self::KEY_SIZE, Self::KEY_SIZE, fill);
+            draw_rectangle_lines(x, y, Self::KEY_SIZE, Self::KEY_SIZE, 2.0, theme.text);
+
+            // Незадействованное управление отмечаем бледным значком.
+            let mut color = theme.text;
+            color.a = if level > 0.0 { 1.0 } else { 0.3 };
+            let center = Vec2::new(x, y) + Vec2::splat(Self::KEY_SIZE / 2.0);
+            Self::draw_symbol(index, center, color);
+        }
+    }
+
+    /// Значок индикатора с номером `index`: стрелки влево, вправо и вверх (форсаж)
+    /// и круг (выстрел). Значки не зависят от языка и назначения клавиш.
+    fn draw_symbol(index: usize, center: Vec2, color: Color) {
+        let size = Self::KEY_SIZE * 0.25;
+        let direction = match index {
+            0 => Vec2::new(-1.0, 0.0),
+            1 => Vec2::new(1.0, 0.0),
+            2 => Vec2::new(0.0, -1.0),
+            _ => {
+                draw_circle(center.x, center.y, size, color);
+                return;
+            }
+        };
+        let tip = center + direction * size;
+        let side = direction.perp() * size;
+        let back = center - direction * size;
+        draw_triangle(tip, back + side, back - side, color);
+    }
+}
@@ -0,0 +1,395 @@
+//! Локализация текста интерфейса.
+
+/// Язык интерфейса.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    /// Английский.
+    English,
+    /// Русский.
+    Russian,
+}
+
+/// Строки интерфейса. Строки с `{}` - шаблоны, в которые подставляется значение.
+#[derive(Clone, Copy)]
+pub enum TextKey {
+    MenuHelp,
+    MenuPlay,
+    MenuPractice,
+    MenuDaily,
+    MenuTimeAttack,
+    MenuReplay,
+    MenuOptions,
+    MenuLeaderboard,
+    MenuStats,
+    MenuQuit,
+    FlightModeHint,
+    VolumeHint,
+    Leaderboard,
+    NoResults,
+    LeaderboardBack,
+    Stats,
+    GamesPlayed,
+    TotalTime,
+    AverageTime,
+    TotalDodged,
+    TotalDestroyed,
+    StatsBack,
+    Options,
+    Volume,
+    Difficulty,
+    Language,
+    Edges,
+    Steering,
+    Theme,
+    Spawning,
+    Vertical,
+    DeadZone,
+    Curve,
+    Shapes,
+    FrameLimit,
+    Controls,
+    OptionsHelp,
+    GameOver,
+    GoalReached,
+    ContinueOffer,
+    ContinueYes,
+    ContinueNo,
+    ReachedWithLives,
+    Goal,
+    TimeLeft,
+    YourTime,
+    BestTime,
+    NewRecord,
+    Dodged,
+    Score,
+    Best,
+    ReturnToMenu,
+    WatchReplay,
+    NewHighScore,
+    InitialsHelp,
+    Paused,
+    PauseResume,
+    PauseRestart,
+    PauseEnd,
+    Replay,
+    Practice,
+    Daily,
+    PracticeHelp,
+    Slow,
+    Boost,
+    Lives,
+    Streak,
+    StreakMilestone,
+    Bombs,
+    Wave,
+    WaveCleared,
+    WavesCleared,
+    Seed,
+    SeedRandom,
+    ActionLeft,
+    ActionRight,
+    ActionThrust,
+    ActionDown,
+    ActionFire,
+    ActionSlow,
+    ActionBomb,
+    ActionPause,
+    ActionQuit,
+    ActionStart,
+    ModeClassic,
+    ModeFree,
+    DifficultyEasy,
+    DifficultyNormal,
+    DifficultyHard,
+    DifficultyInsane,
+    ChooseDifficulty,
+    ChooseDifficultyHelp,
+    EdgesClamp,
+    EdgesWrap,
+    SteeringKeyboard,
+    SteeringMouse,
+    ThemeDark,
+    ThemeLight,
+    ThemeNeon,
+    ThemeContrast,
+    SpawningStream,
+    SpawningWaves,
+    Off,
+    On,
+    CurveLinear,
+    CurveQuadratic,
+}
+
+impl Lang {
+    /// Следующий язык (для переключения в настройках).
+    pub fn next(self) -> Self {
+        match self {
+            Lang::English => Lang::Russian,
+            Lang::Russian => Lang::English,
+        }
+    }
+
+    /// Название языка на нём самом.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lang::English => "English",
+            Lang::Russian => "Русский",
+        }
+    }
+
+    /// Название языка в файле настроек.
+    pub fn key(self) -> &'static str {
+        match self {
+            Lang::English => "en",
+            Lang::Russian => "ru",
+        }
+    }
+
+    /// Язык по его названию в файле, см. [`Lang::key`].
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "en" => Some(Lang::English),
+            "ru" => Some(Lang::Russian),
+            _ => None,
+        }
+    }
+
+    /// Строка интерфейса `key` на этом языке.
+    pub fn text(&self, key: TextKey) -> &'static str {
+        match self {
+            Lang::English => english(key),
+            Lang::Russian => russian(key),
+        }
+    }
+
+    /// Шаблон `key` на этом языке с подставленным значением `value`.
+    pub fn format(&self, key: TextKey, value: impl std::fmt::Display) -> String {
+        self.text(key).replace("{}", &value.to_string())
+    }
+}
+
+/// Строки интерфейса на английском.
+fn english(key: TextKey) -> &'static str {
+    match key {
+        TextKey::MenuHelp => "Up/Down: choose, {}: select",
+        TextKey::MenuPlay => "Play",
+        TextKey::MenuPractice => "Practice (T)",
+        TextKey::MenuDaily => "Daily challenge (C), today's best: {}",
+        TextKey::MenuTimeAttack => "Time attack (G), best: {}",
+        TextKey::MenuReplay => "Watch the last game (R)",
+        TextKey::MenuOptions => "Options (O)",
+        TextKey::MenuLeaderboard => "Leaderboard (L)",
+        TextKey::MenuStats => "Statistics (S)",
+        TextKey::MenuQuit => "Quit",
+        TextKey::FlightModeHint => "Flight mode (F)",
+        TextKey::VolumeHint => "Volume (-/=)",
+        TextKey::Leaderboard => "Leaderboard",
+        TextKey::NoResults => "No results yet.",
+        TextKey::LeaderboardBack => "Press L to return to menu.",
+        TextKey::Stats => "Statistics",
+        TextKey::GamesPlayed => "Games played",
+        TextKey::TotalTime => "Total time",
+        TextKey::AverageTime => "Average game",
+        TextKey::TotalDodged => "Asteroids dodged",
+        TextKey::TotalDestroyed => "Asteroids destroyed",
+        TextKey::StatsBack => "Press S to return to menu.",
+        TextKey::Options => "Options",
+        TextKey::Volume => "Volume",
+        TextKey::Difficulty => "Difficulty",
+        TextKey::Language => "Language",
+        TextKey::Edges => "Screen edges",
+        TextKey::Steering => "Steering",
+        TextKey::Theme => "Theme",
+        TextKey::Spawning => "Asteroids",
+        TextKey::Vertical => "Vertical movement (classic)",
+        TextKey::DeadZone => "Stick dead zone",
+        TextKey::Curve => "Stick response",
+        TextKey::Shapes => "Tell objects apart by shape",
+        TextKey::FrameLimit => "Frame rate limit",
+        TextKey::Controls => "Controls",
+        TextKey::OptionsHelp => "Up/Down - select, Left/Right or digits - change, O - back.",
+        TextKey::GameOver => "Game Over",
+        TextKey::GoalReached => "Goal reached!",
+        TextKey::ContinueOffer => "Continue for {} points?",
+        TextKey::ContinueYes => "continue",
+        TextKey::ContinueNo => "give up",
+        TextKey::ReachedWithLives => "goal reached, lives left: {}",
+        TextKey::Goal => "Goal",
+        TextKey::TimeLeft => "Time left",
+        TextKey::YourTime => "Your time",
+        TextKey::BestTime => "Best time",
+        TextKey::NewRecord => "New record!",
+        TextKey::Dodged => "Asteroids dodged",
+        TextKey::Score => "Score",
+        TextKey::Best => "best",
+        TextKey::ReturnToMenu => "Press {} to return to menu.",
+        TextKey::WatchReplay => "Press R to watch the replay.",
+        TextKey::NewHighScore => "New high score!",
+        TextKey::InitialsHelp => "Up/Down - change letter, Left/Right - move, {} - confirm.",
+        TextKey::Paused => "PAUSED",
+        TextKey::PauseResume => "Resume",
+        TextKey::PauseRestart => "Restart",
+        TextKey::PauseEnd => "End game",
+        TextKey::Replay => "REPLAY",
+        TextKey::Practice => "PRACTICE",
+        TextKey::Daily => "DAILY CHALLENGE",
+        TextKey::PracticeHelp => "Click - new asteroid, mouse wheel - its speed: {}",
+        TextKey::Slow => "Slow",
+        TextKey::Boost => "Boost",
+        TextKey::Lives => "Lives",
+        TextKey::Streak => "streak",
+        TextKey::StreakMilestone => "{} s without a hit!",
+        TextKey::Bombs => "Bombs",
+        TextKey::Wave => "Wave",
+        TextKey::WaveCleared => "Wave {} cleared!",
+        TextKey::WavesCleared => "Waves cleared",
+        TextKey::Seed => "Seed",
+        TextKey::SeedRandom => "Random",
+        TextKey::ActionLeft => "Left",
+        TextKey::ActionRight => "Right",
+        TextKey::ActionThrust => "Thrust / boost / up",
+        TextKey::ActionDown => "Down",
+        TextKey::ActionFire => "Fire",
+        TextKey::ActionSlow => "Slow motion",
+        TextKey::ActionBomb => "Bomb",
+        TextKey::ActionPause => "Pause",
+        TextKey::ActionQuit => "Quit",
+        TextKey::ActionStart => "Start",
+        TextKey::ModeClassic => "Classic",
+        TextKey::ModeFree => "Free flight",
+        TextKey::DifficultyEasy => "Easy",
+        TextKey::DifficultyNormal => "Normal",
+        TextKey::DifficultyHard => "Hard",
+        TextKey::DifficultyInsane => "Insane",
+        TextKey::ChooseDifficulty => "Choose difficulty",
+        TextKey::ChooseDifficultyHelp => "Press {} to start, Escape to go back.",
+        TextKey::EdgesClamp => "Stop",
+        TextKey::EdgesWrap => "Wrap around",
+        TextKey::SteeringKeyboard => "Keyboard",
+        TextKey::SteeringMouse => "Mouse",
+        TextKey::ThemeDark => "Dark",
+        TextKey::ThemeLight => "Light",
+        TextKey::ThemeNeon => "Neon",
+        TextKey::ThemeContrast => "High contrast",
+        TextKey::SpawningStream => "Endless stream",
+        TextKey::SpawningWaves => "Waves",
+        TextKey::Off => "Off",
+        TextKey::On => "On",
+        TextKey::CurveLinear => "Linear",
+        TextKey::CurveQuadratic => "Quadratic",
+    }
+}
+
+/// Строки интерфейса на русском.
+fn russian(key: TextKey) -> &'static str {
+    match key {
+        TextKey::MenuHelp => "Вверх/вниз: выбор, {}: подтвердить",
+        TextKey::MenuPlay => "Играть",
+        TextKey::MenuPractice => "Тренировка (T)",
+        TextKey::MenuDaily => "Испытание дня (C), лучшее сегодня: {}",
+        TextKey::MenuTimeAttack => "На время (G), лучшее: {}",
+        TextKey::MenuReplay => "Посмотреть последнюю игру (R)",
+        TextKey::MenuOptions => "Настройки (O)",
+        TextKey::MenuLeaderboard => "Таблица рекордов (L)",
+        TextKey::MenuStats => "Статистика (S)",
+        TextKey::MenuQuit => "Выход",
+        TextKey::FlightModeHint => "Режим полёта (F)",
+        TextKey::VolumeHint => "Громкость (-/=)",
+        TextKey::Leaderboard => "Таблица рекордов",
+        TextKey::NoResults => "Результатов пока нет.",
+        TextKey::LeaderboardBack => "Нажмите L, чтобы вернуться в меню.",
+        TextKey::Stats => "Статистика",
+        TextKey::GamesPlayed => "Сыграно игр",
+        TextKey::TotalTime => "Общее время",
+        TextKey::AverageTime => "Средняя игра",
+        TextKey::TotalDodged => "Астероидов пропущено",
+        TextKey::TotalDestroyed => "Астероидов уничтожено",
+        TextKey::StatsBack => "Нажмите S, чтобы вернуться в меню.",
+        TextKey::Options => "Настройки",
+        TextKey::Volume => "Громкость",
+        TextKey::Difficulty => "Сложность",
+        TextKey::Language => "Язык",
+        TextKey::Edges => "Края экрана",
+        TextKey::Steering => "Управление кораблём",
+        TextKey::Theme => "Цветовая тема",
+        TextKey::Spawning => "Астероиды",
+        TextKey::Vertical => "Движение по вертикали (классика)",
+        TextKey::DeadZone => "Мёртвая зона стика",
+        TextKey::Curve => "Отклик стика",
+        TextKey::Shapes => "Различать объекты по форме",
+        TextKey::FrameLimit => "Ограничение частоты кадров",
+        TextKey::Controls => "Управление",
+        TextKey::OptionsHelp => {
+            "Вверх/вниз - выбор, влево/вправо или цифры - изменение, O - назад."
+        }
+        TextKey::GameOver => "Игра окончена",
+        TextKey::GoalReached => "Цель достигнута!",
+        TextKey::ContinueOffer => "Продолжить за {} очков?",
+        TextKey::ContinueYes => "продолжить",
+        TextKey::ContinueNo => "сдаться",
+        TextKey::ReachedWithLives => "цель достигнута, жизней осталось: {}",
+        TextKey::Goal => "Цель",
+        TextKey::TimeLeft => "Осталось",
+        TextKey::YourTime => "Ваше время",
+        TextKey::BestTime => "Лучшее время",
+        TextKey::NewRecord => "Новый рекорд!",
+        TextKey::Dodged => "Астероидов пролетело мимо",
+        TextKey::Score => "Очки",
+        TextKey::Best => "лучший",
+        TextKey::ReturnToMenu => "Нажмите {}, чтобы вернуться в меню.",
+        TextKey::WatchReplay => "Нажмите R, чтобы посмотреть запись.",
+        TextKey::NewHighScore => "Результат попал в таблицу!",
+        TextKey::InitialsHelp => "Вверх/вниз - буква, влево/вправо - позиция, {} - готово.",
+        TextKey::Paused => "ПАУЗА",
+        TextKey::PauseResume => "Продолжить",
+        TextKey::PauseRestart => "Начать заново",
+        TextKey::PauseEnd => "Завершить игру",
+        TextKey::Replay => "ПОВТОР",
+        TextKey::Practice => "ТРЕНИРОВКА",
+        TextKey::Daily => "ИСПЫТАНИЕ ДНЯ",
+        TextKey::PracticeHelp => "Щелчок - новый астероид, колесо мыши - его скорость: {}",
+        TextKey::Slow => "Замедление",
+        TextKey::Boost => "Форсаж",
+        TextKey::Lives => "Жизни",
+        TextKey::Streak => "без столкновений",
+        TextKey::StreakMilestone => "{} с без столкновений!",
+        TextKey::Bombs => "Бомбы",
+        TextKey::Wave => "Волна",
+        TextKey::WaveCleared => "Волна {} пройдена!",
+        TextKey::WavesCleared => "Пройдено волн",
+        TextKey::Seed => "Зерно",
+        TextKey::SeedRandom => "Случайное",
+        TextKey::ActionLeft => "Влево",
+        TextKey::ActionRight => "Вправо",
+        TextKey::ActionThrust => "Тяга / форсаж / вверх",
+        TextKey::ActionDown => "Вниз",
+        TextKey::ActionFire => "Выстрел",
+        TextKey::ActionSlow => "Замедление",
+        TextKey::ActionBomb => "Бомба",
+        TextKey::ActionPause => "Пауза",
+        TextKey::ActionQuit => "Выход",
+        TextKey::ActionStart => "Старт",
+        TextKey::ModeClassic => "Классический",
+        TextKey::ModeFree => "Свободный полёт",
+        TextKey::DifficultyEasy => "Лёгкая",
+        TextKey::DifficultyNormal => "Обычная",
+        TextKey::DifficultyHard => "Сложная",
+        TextKey::DifficultyInsane => "Безумная",
+        TextKey::ChooseDifficulty => "Выберите сложность",
+        TextKey::ChooseDifficultyHelp => "Нажмите {}, чтобы начать, или Escape, чтобы вернуться.",
+        TextKey::EdgesClamp => "Останавливают",
+        TextKey::EdgesWrap => "Переносят на другую сторону",
+        TextKey::SteeringKeyboard => "Клавиатура",
+        TextKey::SteeringMouse => "Мышь",
+        TextKey::ThemeDark => "Тёмная",
+        TextKey::ThemeLight => "Светлая",
+        TextKey::ThemeNeon => "Неон",
+        TextKey::ThemeContrast => "Контрастная",
+        TextKey::SpawningStream => "Бесконечный поток",
+        TextKey::SpawningWaves => "Волнами",
+        TextKey::Off => "Выключено",
+        TextKey::On => "Включено",
+        TextKey::CurveLinear => "Линейный",
+        TextKey::CurveQuadratic => "Квадратичный",
+    }
+}
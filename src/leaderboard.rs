@@ -0,0 +1,88 @@
+//! Таблица лучших результатов.
+
+use crate::storage;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Сохранение (см. [`storage`]), в котором хранится таблица результатов.
+const LEADERBOARD_FILE: &str = "leaderboard.json";
+
+/// Результат в таблице.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// Инициалы игрока из трёх латинских букв.
+    pub initials: String,
+    /// Время, которое игроку удалось продержаться.
+    pub time: f64,
+    /// День игры по UTC в виде числа `ГГГГММДД`. В таблицах, сохранённых до появления
+    /// дат, дня нет, и он равен нулю.
+    #[serde(default)]
+    pub date: u32,
+}
+
+/// Таблица лучших результатов, упорядоченная по убыванию времени.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Leaderboard {
+    entries: Vec<Entry>,
+}
+
+impl Leaderboard {
+    /// Наибольшее число результатов в таблице.
+    const MAX_ENTRIES: usize = 10;
+
+    /// Попадает ли в таблицу результат со временем `time`.
+    pub fn qualifies(&self, time: f64) -> bool {
+        self.entries.len() < Self::MAX_ENTRIES
+            || self.entries.last().is_some_and(|last| time > last.time)
+    }
+
+    /// Добавление результата. Если результатов стало больше десяти, худший отбрасывается.
+    pub fn insert(&mut self, entry: Entry) {
+        // Равный результат ставим ниже уже имеющегося: он показан позже.
+        let index = self
+            .entries
+            .partition_point(|other| other.time >= entry.time);
+        self.entries.insert(index, entry);
+        self.entries.truncate(Self::MAX_ENTRIES);
+    }
+
+    /// Результаты от лучшего к худшему.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Загрузка таблицы из файла. Если файла нет или он повреждён, то таблица пуста.
+    pub fn load() -> Self {
+        let text = match storage::read(LEADERBOARD_FILE) {
+            Some(text) => text,
+            None => return Self::default(),
+        };
+        match serde_json::from_str::<Self>(&text) {
+            Ok(mut leaderboard) => {
+                // Файл могли отредактировать вручную, поэтому восстанавливаем порядок.
+                let entries = std::mem::take(&mut leaderboard.entries);
+                for entry in entries {
+                    leaderboard.insert(entry);
+                }
+                leaderboard
+            }
+            Err(err) => {
+                warn!("Failed to parse leaderboard: {}", err);
+                Self::default()
+            }
+        }
+    }
+
+    /// Сохранение таблицы в файл. Ошибка записи не прерывает игру.
+    pub fn save(&self) {
+        let result = serde_json::to_string_pretty(self)
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                storage::write(LEADERBOARD_FILE, &text).map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            warn!("Failed to save leaderboard: {}", err);
+        }
+    }
+}
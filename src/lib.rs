@@ -0,0 +1,42 @@
+//! Логика игры: состояние приложения, игровой мир, ввод, настройки и интерфейс.
+//!
+//! Вынесена в библиотеку, чтобы её можно было использовать из тестов и других
+//! исполняемых файлов. Сама игра запускается из `main.rs`.
+
+pub mod assets;
+pub mod asteroid;
+pub mod autopilot;
+pub mod bullet;
+pub mod config;
+pub mod daily;
+pub mod difficulty;
+pub mod game;
+pub mod ghost;
+pub mod gravity;
+pub mod input;
+pub mod input_display;
+pub mod lang;
+pub mod leaderboard;
+pub mod overlay;
+pub mod particle;
+pub mod pool;
+pub mod popup;
+pub mod powerup;
+pub mod quality;
+pub mod radar;
+pub mod replay;
+pub mod rng;
+pub mod seeker;
+pub mod settings;
+pub mod ship;
+pub mod sound;
+pub mod spatial;
+pub mod starfield;
+pub mod state;
+pub mod stats;
+pub mod storage;
+pub mod theme;
+pub mod threat;
+pub mod time_attack;
+pub mod ui;
+pub mod weapons;
@@ -0,0 +1,148 @@
+//! Отладочный оверлей с частотой кадров и числом объектов и отдалённый вид,
+//! показывающий, где астероиды появляются и исчезают за пределами экрана.
+//!
+//! Нужен только при настройке игры, поэтому собран в одном месте:
+//! чтобы убрать его, достаточно удалить этот модуль и строки, где он используется.
+
+use crate::ui::{draw_ui_text, measure_ui_text};
+use macroquad::prelude::*;
+
+/// Счётчики объектов, отображаемые оверлеем.
+pub struct ObjectCounts {
+    /// Число астероидов.
+    pub asteroids: usize,
+    /// Число частиц взрывов.
+    pub particles: usize,
+}
+
+/// Отображаемый оверлеем уровень качества.
+pub struct QualityInfo {
+    /// Название уровня.
+    pub name: &'static str,
+    /// Наибольшее число частиц на этом уровне.
+    pub max_particles: usize,
+}
+
+/// Границы игрового мира, обводимые в отдалённом виде.
+/// В выпускной сборке отдалённого вида нет, и границы не используются.
+pub struct WorldBounds {
+    /// Размер экрана.
+    pub screen_size: Vec2,
+    /// Высота, на которой появляются астероиды (над экраном, поэтому отрицательная).
+    pub spawn_y: f32,
+    /// Насколько астероид может выйти за левый, правый и нижний края экрана,
+    /// прежде чем исчезнуть.
+    pub margin: f32,
+}
+
+/// Отладочный оверлей. По умолчанию скрыт, переключается клавишей F3.
+///
+/// В отладочной сборке клавиша F4 вдобавок отдаляет камеру, так что видны области
+/// за пределами экрана, а экран и границы появления и исчезновения астероидов обведены.
+#[derive(Default)]
+pub struct DebugOverlay {
+    /// Показан ли оверлей.
+    visible: bool,
+    /// Длительность последнего кадра в секундах до ограничения и замедления.
+    frame_time: f32,
+    /// Отдалена ли камера.
+    #[cfg(debug_assertions)]
+    zoomed_out: bool,
+}
+
+impl DebugOverlay {
+    /// Переключение оверлея и учёт длительности кадра.
+    pub fn update(&mut self) {
+        if is_key_pressed(KeyCode::F3) {
+            self.visible = !self.visible;
+        }
+        #[cfg(debug_assertions)]
+        if is_key_pressed(KeyCode::F4) {
+            self.zoomed_out = !self.zoomed_out;
+        }
+        self.frame_time = get_frame_time();
+    }
+
+    /// Показан ли оверлей. Вместе с ним показываются и другие отладочные пометки.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Камера отдалённого вида, если он включён: вмещает экран вместе с местом
+    /// появления астероидов и границами их исчезновения `bounds`, сохраняя пропорции экрана.
+    #[cfg(debug_assertions)]
+    pub fn zoomed_out_camera(&self, bounds: &WorldBounds) -> Option<Camera2D> {
+        /// Запас вокруг границ, чтобы их обводка не прилегала к краям окна.
+        const PADDING: f32 = 1.1;
+
+        if !self.zoomed_out {
+            return None;
+        }
+        let screen = bounds.screen_size;
+        let min = Vec2::new(-bounds.margin, bounds.spawn_y.min(-bounds.margin));
+        let max = screen + Vec2::splat(bounds.margin);
+        // Отдаляем камеру от центра экрана так, чтобы самая дальняя граница поместилась.
+        let center = screen / 2.0;
+        let extent = (center - min).max(max - center);
+        let zoom = (extent / center).max_element() * PADDING;
+        let size = screen * zoom;
+        let corner = center - size / 2.0;
+        let rect = Rect::new(corner.x, corner.y, size.x, size.y);
+        Some(Camera2D::from_display_rect(rect))
+    }
+
+    /// В выпускной сборке отдалённого вида нет.
+    #[cfg(not(debug_assertions))]
+    pub fn zoomed_out_camera(&self, _bounds: &WorldBounds) -> Option<Camera2D> {
+        None
+    }
+
+    /// Обводка экрана (жёлтым), линии появления астероидов (зелёным) и границ
+    /// их исчезновения (красным) в координатах игрового мира, если включён отдалённый вид.
+    #[cfg(debug_assertions)]
+    pub fn draw_bounds(&self, bounds: &WorldBounds) {
+        const THICKNESS: f32 = 3.0;
+
+        if !self.zoomed_out {
+            return;
+        }
+        let (width, height) = (bounds.screen_size.x, bounds.screen_size.y);
+        draw_rectangle_lines(0.0, 0.0, width, height, THICKNESS, YELLOW);
+        draw_line(0.0, bounds.spawn_y, width, bounds.spawn_y, THICKNESS, GREEN);
+
+        // Сверху границы нет: астероиды появляются над экраном и летят вниз.
+        let (left, right) = (-bounds.margin, width + bounds.margin);
+        let (top, bottom) = (bounds.spawn_y.min(-bounds.margin), height + bounds.margin);
+        draw_line(left, top, left, bottom, THICKNESS, RED);
+        draw_line(right, top, right, bottom, THICKNESS, RED);
+        draw_line(left, bottom, right, bottom, THICKNESS, RED);
+    }
+
+    /// В выпускной сборке отдалённого вида нет.
+    #[cfg(not(debug_assertions))]
+    pub fn draw_bounds(&self, _bounds: &WorldBounds) {}
+
+    /// Отображение оверлея в правом верхнем углу, если он включён.
+    pub fn draw(&self, counts: ObjectCounts, quality: QualityInfo) {
+        if !self.visible {
+            return;
+        }
+
+        let lines = [
+            format!("FPS: {}", get_fps()),
+            format!("Frame: {:.1} ms", self.frame_time * 1000.0),
+            format!("Asteroids: {}", counts.asteroids),
+            format!(
+                "Particles: {} / {}",
+                counts.particles, quality.max_particles
+            ),
+            format!("Quality: {}", quality.name),
+        ];
+        let font_size = 20.0;
+        for (index, line) in lines.iter().enumerate() {
+            let width = measure_ui_text(line, font_size).width;
+            let y = 50.0 + font_size * index as f32;
+            draw_ui_text(line, screen_width() - width - 10.0, y, font_size, YELLOW);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Частицы взрывов и выхлопа двигателя.
+
+use macroquad::prelude::*;
+use macroquad::rand::RandomRange;
+use serde::{Deserialize, Serialize};
+
+/// Частица: угасающий к концу жизни круг, летящий с постоянной скоростью.
+#[derive(Serialize, Deserialize)]
+pub struct Particle {
+    position: Vec2,
+    velocity: Vec2,
+    /// Оставшееся время жизни в секундах.
+    lifetime: f32,
+    /// Полное время жизни в секундах, от которого отсчитывается угасание.
+    max_lifetime: f32,
+    radius: f32,
+    #[serde(with = "ColorDef")]
+    color: Color,
+}
+
+/// Описание цвета macroquad для сериализации: сам он её не поддерживает.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Color")]
+struct ColorDef {
+    r: f32,
+    g: f32,
+    b: f32,
+    a: f32,
+}
+
+impl Particle {
+    // Параметры частиц взрыва.
+    const LIFETIME: f32 = 0.8;
+    const MAX_SPEED: f32 = 200.0;
+    const RADIUS: f32 = 3.0;
+    const EXPLOSION_SIZE: usize = 15;
+
+    /// Создание частицы в точке `position` со скоростью `velocity`, живущей `lifetime` секунд.
+    pub fn new(position: Vec2, velocity: Vec2, lifetime: f32, radius: f32, color: Color) -> Self {
+        Self {
+            position,
+            velocity,
+            lifetime,
+            max_lifetime: lifetime,
+            radius,
+            color,
+        }
+    }
+
+    /// Обновление состояния частицы.
+    pub fn update(&mut self, elapsed_time: f64) {
+        let elapsed_time = elapsed_time as f32;
+        self.position += self.velocity * elapsed_time;
+        self.lifetime -= elapsed_time;
+    }
+
+    /// Перенос частицы в окно изменившегося размера.
+    pub fn rescale(&mut self, scale: Vec2) {
+        self.position *= scale;
+    }
+
+    /// Не угасла ли ещё частица.
+    pub fn is_alive(&self) -> bool {
+        self.lifetime > 0.0
+    }
+
+    /// Отображение частицы в виде круга, угасающего к концу жизни.
+    pub fn draw(&self) {
+        let fade = (self.lifetime / self.max_lifetime).clamp(0.0, 1.0);
+        let color = Color::new(
+            self.color.r,
+            self.color.g,
+            self.color.b,
+            self.color.a * fade,
+        );
+        draw_circle(self.position.x, self.position.y, self.radius, color);
+    }
+}
+
+/// Система частиц: создаёт частицы вспышками и по одной, обновляет и отображает их.
+///
+/// Угасшие частицы удаляются из вектора на месте, поэтому после того, как он вырос
+/// до наибольшего числа частиц, память больше не выделяется. Частиц не бывает больше
+/// `max`: лишние гаснут раньше срока, начиная со старых.
+#[derive(Serialize, Deserialize)]
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    max: usize,
+}
+
+impl ParticleSystem {
+    /// Создание пустой системы, в которой одновременно живут не больше `max` частиц.
+    pub fn new(max: usize) -> Self {
+        Self {
+            particles: Vec::with_capacity(max),
+            max,
+        }
+    }
+
+    /// Изменение наибольшего числа частиц. Лишние погаснут при следующем обновлении.
+    pub fn set_max(&mut self, max: usize) {
+        self.max = max;
+    }
+
+    /// Число живых частиц.
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Нет ли живых частиц.
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Добавление одной частицы, например для выхлопа или следа.
+    pub fn emit(&mut self, particle: Particle) {
+        self.particles.push(particle);
+    }
+
+    /// Вспышка из `count` частиц цвета `color`, разлетающихся во все стороны из точки
+    /// `position` со скоростью до `max_speed` и живущих до `lifetime` секунд.
+    pub fn burst(
+        &mut self,
+        position: Vec2,
+        color: Color,
+        count: usize,
+        max_speed: f32,
+        lifetime: f32,
+    ) {
+        self.particles.extend((0..count).map(|index| {
+            // Распределяем частицы по кругу, немного сбивая направление и скорость.
+            let angle =
+                index as f32 / count as f32 * std::f32::consts::TAU + f32::gen_range(-0.2, 0.2);
+            let speed = f32::gen_range(max_speed / 3.0, max_speed);
+            let velocity = Vec2::new(angle.cos(), angle.sin()) * speed;
+            let lifetime = f32::gen_range(lifetime / 2.0, lifetime);
+            Particle::new(position, velocity, lifetime, Particle::RADIUS, color)
+        }));
+    }
+
+    /// Взрыв астероида или корабля в точке `position`.
+    pub fn explosion(&mut self, position: Vec2, color: Color) {
+        self.burst(
+            position,
+            color,
+            Particle::EXPLOSION_SIZE,
+            Particle::MAX_SPEED,
+            Particle::LIFETIME,
+        );
+    }
+
+    /// Обновление частиц и удаление угасших, а если частиц больше `max` - самых старых.
+    pub fn update(&mut self, elapsed_time: f64) {
+        for particle in &mut self.particles {
+            particle.update(elapsed_time);
+        }
+        self.particles.retain(Particle::is_alive);
+        let excess = self.particles.len().saturating_sub(self.max);
+        self.particles.drain(..excess);
+    }
+
+    /// Перенос частиц в окно изменившегося размера.
+    pub fn rescale(&mut self, scale: Vec2) {
+        for particle in &mut self.particles {
+            particle.rescale(scale);
+        }
+    }
+
+    /// Отображение частиц.
+    pub fn draw(&self) {
+        for particle in &self.particles {
+            particle.draw();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_particles_fade_first_above_max() {
+        let mut system = ParticleSystem::new(3);
+        for lifetime in [1.0, 2.0, 3.0, 4.0] {
+            system.emit(Particle::new(Vec2::ZERO, Vec2::X, lifetime, 1.0, WHITE));
+        }
+        // Сверх наибольшего числа гаснет самая старая частица.
+        system.update(0.5);
+        assert_eq!(system.len(), 3);
+        // Угасшие по времени частицы удаляются.
+        system.update(3.0);
+        assert_eq!(system.len(), 1);
+        assert_eq!(system.particles[0].max_lifetime, 4.0);
+    }
+}
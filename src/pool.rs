@@ -0,0 +1,139 @@
+//! Пул объектов, переиспользующий место уничтоженных объектов.
+
+use serde::{Deserialize, Serialize};
+
+/// Ячейка пула.
+#[derive(Serialize, Deserialize)]
+struct Slot<T> {
+    item: T,
+    /// Занята ли ячейка живым объектом.
+    active: bool,
+}
+
+/// Пул объектов.
+///
+/// Уничтоженные объекты не удаляются из вектора, а только помечаются неактивными,
+/// и их ячейки занимают новые объекты. Поэтому после того, как пул вырос до наибольшего
+/// числа одновременно живущих объектов, добавление и удаление не выделяют память,
+/// а индексы живых объектов не меняются.
+#[derive(Serialize, Deserialize)]
+pub struct Pool<T> {
+    slots: Vec<Slot<T>>,
+    /// Индексы свободных ячеек.
+    free: Vec<usize>,
+}
+
+impl<T> Pool<T> {
+    /// Создание пустого пула, вмещающего `capacity` объектов без дополнительных аллокаций.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            free: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Добавление объекта в свободную ячейку. Если свободных ячеек нет, пул растёт.
+    /// Возвращает индекс ячейки.
+    pub fn spawn(&mut self, item: T) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = Slot { item, active: true };
+                index
+            }
+            None => {
+                self.slots.push(Slot { item, active: true });
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Освобождение ячейки с индексом `index`.
+    pub fn release(&mut self, index: usize) {
+        let slot = &mut self.slots[index];
+        if slot.active {
+            slot.active = false;
+            self.free.push(index);
+        }
+    }
+
+    /// Освобождение ячеек всех объектов, для которых `keep` возвращает `false`.
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.active && !keep(&slot.item) {
+                slot.active = false;
+                self.free.push(index);
+            }
+        }
+    }
+
+    /// То же, что [`Pool::retain`], но `keep` может изменять объекты.
+    pub fn retain_mut(&mut self, mut keep: impl FnMut(&mut T) -> bool) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.active && !keep(&mut slot.item) {
+                slot.active = false;
+                self.free.push(index);
+            }
+        }
+    }
+
+    /// Число живых объектов.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Нет ли живых объектов.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Живые объекты вместе с индексами их ячеек.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.active)
+            .map(|(index, slot)| (index, &slot.item))
+    }
+
+    /// Изменяемые живые объекты.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.slots
+            .iter_mut()
+            .filter(|slot| slot.active)
+            .map(|slot| &mut slot.item)
+    }
+
+    /// Два разных объекта одновременно для изменения. Индекс `first` должен быть меньше `second`.
+    #[cfg(feature = "asteroid-collisions")]
+    pub fn pair_mut(&mut self, first: usize, second: usize) -> (&mut T, &mut T) {
+        let (head, tail) = self.slots.split_at_mut(second);
+        (&mut head[first].item, &mut tail[0].item)
+    }
+}
+
+impl<T> std::ops::Index<usize> for Pool<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.slots[index].item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_reuses_released_slots() {
+        let mut pool = Pool::with_capacity(2);
+        let first = pool.spawn(1);
+        let second = pool.spawn(2);
+        pool.release(first);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.spawn(3), first);
+        pool.retain(|&item| item != 2);
+        assert_eq!(pool.spawn(4), second);
+        let items: Vec<_> = pool.iter().map(|(_, &item)| item).collect();
+        assert_eq!(items, [3, 4]);
+    }
+}
@@ -0,0 +1,62 @@
+//! Всплывающие надписи с начисленными очками.
+
+use crate::ui::{draw_ui_text, measure_ui_text};
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Надпись, всплывающая вверх и угасающая.
+#[derive(Serialize, Deserialize)]
+pub struct Popup {
+    /// Начисленные очки. Надпись составляется только при отображении,
+    /// чтобы создание надписи в кадре игры не выделяло память.
+    points: u64,
+    /// Положение центра надписи.
+    position: Vec2,
+    /// Оставшееся время жизни в секундах.
+    lifetime: f32,
+}
+
+impl Popup {
+    // Параметры надписей.
+    const LIFETIME: f32 = 0.8;
+    /// Скорость всплытия в пикселях в секунду.
+    const RISE_SPEED: f32 = 60.0;
+    const FONT_SIZE: f32 = 20.0;
+
+    /// Создание надписи с начисленными очками `points` с центром в точке `position`.
+    pub fn new(points: u64, position: Vec2) -> Self {
+        Self {
+            points,
+            position,
+            lifetime: Self::LIFETIME,
+        }
+    }
+
+    /// Обновление состояния надписи.
+    pub fn update(&mut self, elapsed_time: f64) {
+        let elapsed_time = elapsed_time as f32;
+        self.position.y -= Self::RISE_SPEED * elapsed_time;
+        self.lifetime -= elapsed_time;
+    }
+
+    /// Перенос надписи в окно изменившегося размера.
+    pub fn rescale(&mut self, scale: Vec2) {
+        self.position *= scale;
+    }
+
+    /// Не угасла ли ещё надпись.
+    pub fn is_alive(&self) -> bool {
+        self.lifetime > 0.0
+    }
+
+    /// Отображение надписи, угасающей к концу жизни.
+    pub fn draw(&self) {
+        let fade = (self.lifetime / Self::LIFETIME).clamp(0.0, 1.0);
+        let color = Color::new(GOLD.r, GOLD.g, GOLD.b, GOLD.a * fade);
+        let text = format!("+{}", self.points);
+        let size = measure_ui_text(&text, Self::FONT_SIZE);
+        let x = self.position.x - size.width / 2.0;
+        let y = self.position.y + size.height / 2.0;
+        draw_ui_text(&text, x, y, Self::FONT_SIZE, color);
+    }
+}
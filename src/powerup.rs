@@ -0,0 +1,115 @@
+//! Усиления, которые корабль может подобрать.
+
+use crate::rng::Rng;
+use crate::theme::Theme;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Вид усиления.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PowerUpKind {
+    /// Щит: на время защищает корабль от одного удара.
+    Shield,
+    /// Замедление: на время замедляет мир, не тратя запас замедления.
+    SlowMotion,
+    /// Множитель: на время удваивает все начисляемые очки.
+    ScoreBoost,
+}
+
+impl PowerUpKind {
+    /// Все виды усилений.
+    pub const ALL: [PowerUpKind; 3] = [
+        PowerUpKind::Shield,
+        PowerUpKind::SlowMotion,
+        PowerUpKind::ScoreBoost,
+    ];
+
+    /// Случайный вид усиления.
+    pub fn random(rng: &mut Rng) -> Self {
+        Self::ALL[(rng.next_u64() % Self::ALL.len() as u64) as usize]
+    }
+
+    /// Цвет, которым отображается усиление.
+    pub fn color(self) -> Color {
+        match self {
+            PowerUpKind::Shield => SKYBLUE,
+            PowerUpKind::SlowMotion => VIOLET,
+            PowerUpKind::ScoreBoost => GOLD,
+        }
+    }
+}
+
+/// Усиление, падающее сверху, как астероид.
+#[derive(Serialize, Deserialize)]
+pub struct PowerUp {
+    position: Vec2,
+    /// Положение перед последним шагом игрового мира, для плавного отображения между шагами.
+    prev_position: Vec2,
+    speed: Vec2,
+    kind: PowerUpKind,
+}
+
+impl PowerUp {
+    // Параметры усилений.
+    pub const RADIUS: f32 = 12.0;
+    const MAX_SIDE_SPEED: f32 = 50.0;
+    const FALL_SPEED: f32 = 100.0;
+
+    /// Создание усиления вида `kind` в случайном месте над экраном шириной `width`.
+    pub fn new(rng: &mut Rng, kind: PowerUpKind, width: f32) -> Self {
+        let x = rng.gen_range(0.0, width);
+        let side_speed = rng.gen_range(-Self::MAX_SIDE_SPEED, Self::MAX_SIDE_SPEED);
+        Self {
+            position: Vec2::new(x, -Self::RADIUS),
+            prev_position: Vec2::new(x, -Self::RADIUS),
+            speed: Vec2::new(side_speed, Self::FALL_SPEED),
+            kind,
+        }
+    }
+
+    /// Проверка выхода усиления за границы экрана размером `width` на `height`.
+    pub fn out_of_bounds(&self, width: f32, height: f32) -> bool {
+        let (x, y) = (self.position.x, self.position.y);
+        x < -Self::RADIUS || x > width + Self::RADIUS || y > height + Self::RADIUS
+    }
+
+    /// Обновление состояния усиления. Как и астероиды, усиления движутся навстречу кораблю.
+    pub fn update(&mut self, elapsed_time: f64, ship_speed: f32) {
+        let elapsed_time = elapsed_time as f32;
+        self.prev_position = self.position;
+        self.position += self.speed * elapsed_time;
+        self.position.y += ship_speed * elapsed_time;
+    }
+
+    /// Перенос усиления в окно изменившегося размера.
+    pub fn rescale(&mut self, scale: Vec2) {
+        self.position *= scale;
+        self.prev_position *= scale;
+    }
+
+    /// Положение центра усиления.
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Вид усиления.
+    pub fn kind(&self) -> PowerUpKind {
+        self.kind
+    }
+
+    /// Отображение усиления в виде кольца цвета его вида
+    /// на доле `alpha` пути от предыдущего положения к текущему.
+    pub fn draw(&self, theme: &Theme, alpha: f32) {
+        let position = self.prev_position.lerp(self.position, alpha);
+        let (x, y) = (position.x, position.y);
+        if theme.shapes {
+            // Квадрат не спутать с круглыми астероидами, даже не различая цветов.
+            let (corner, size) = (position - Vec2::splat(Self::RADIUS), 2.0 * Self::RADIUS);
+            draw_rectangle(corner.x, corner.y, size, size, self.kind.color());
+            draw_rectangle_lines(corner.x, corner.y, size, size, 3.0, WHITE);
+        } else {
+            draw_circle(x, y, Self::RADIUS, self.kind.color());
+            draw_circle_lines(x, y, Self::RADIUS, 2.0, WHITE);
+        }
+    }
+}
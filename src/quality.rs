@@ -0,0 +1,129 @@
+//! Подстройка числа частиц и звёзд под производительность машины.
+//!
+//! Если частота кадров падает, частиц взрывов и звёзд фона становится меньше,
+//! а когда запас появляется снова - их число возвращается.
+
+/// Уровень качества: наибольшее число частиц и число звёзд.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct QualityLevel {
+    /// Название для отладочного оверлея.
+    pub name: &'static str,
+    /// Наибольшее число частиц взрывов. Лишние частицы гаснут раньше срока, начиная со старых.
+    pub max_particles: usize,
+    /// Число звёзд на фоне.
+    pub star_count: usize,
+}
+
+/// Уровни качества от высшего к низшему.
+const LEVELS: [QualityLevel; 3] = [
+    QualityLevel {
+        name: "high",
+        max_particles: 1000,
+        star_count: 150,
+    },
+    QualityLevel {
+        name: "medium",
+        max_particles: 300,
+        star_count: 80,
+    },
+    QualityLevel {
+        name: "low",
+        max_particles: 100,
+        star_count: 30,
+    },
+];
+
+/// Подстройка качества по частоте кадров, усреднённой за `WINDOW` секунд.
+///
+/// Качество понижается, если средняя частота ниже `LOW_FPS`, а повышается, только если
+/// она не ниже `HIGH_FPS` несколько окон подряд. Разрыв между порогами и ожидание
+/// не дают качеству скакать туда и обратно.
+#[derive(Default)]
+pub struct Quality {
+    /// Индекс текущего уровня в `LEVELS`.
+    level: usize,
+    /// Сумма частоты кадров за текущее окно, взвешенная по длительности кадров.
+    fps_sum: f32,
+    /// Прошедшая часть текущего окна в секундах.
+    window_time: f32,
+    /// Сколько окон подряд частота была достаточной для повышения качества.
+    good_windows: u32,
+}
+
+impl Quality {
+    /// Длительность окна усреднения в секундах.
+    const WINDOW: f32 = 1.0;
+    /// Ниже этой частоты кадров качество понижается.
+    const LOW_FPS: f32 = 50.0;
+    /// Не ниже этой частоты кадров качество может повыситься.
+    const HIGH_FPS: f32 = 58.0;
+    /// Сколько окон подряд частота должна быть не ниже `HIGH_FPS`, чтобы качество повысилось.
+    const RAISE_WINDOWS: u32 = 3;
+
+    /// Текущий уровень качества.
+    pub fn level(&self) -> QualityLevel {
+        LEVELS[self.level]
+    }
+
+    /// Учёт кадра длительностью `frame_time` с частотой кадров `fps`.
+    /// Возвращает новый уровень, если он изменился.
+    pub fn update(&mut self, fps: f32, frame_time: f32) -> Option<QualityLevel> {
+        self.fps_sum += fps * frame_time;
+        self.window_time += frame_time;
+        if self.window_time < Self::WINDOW {
+            return None;
+        }
+
+        let average = self.fps_sum / self.window_time;
+        self.fps_sum = 0.0;
+        self.window_time = 0.0;
+        if average < Self::LOW_FPS {
+            self.good_windows = 0;
+            if self.level + 1 < LEVELS.len() {
+                self.level += 1;
+                return Some(self.level());
+            }
+        } else if average >= Self::HIGH_FPS {
+            self.good_windows += 1;
+            if self.good_windows >= Self::RAISE_WINDOWS && self.level > 0 {
+                self.good_windows = 0;
+                self.level -= 1;
+                return Some(self.level());
+            }
+        } else {
+            self.good_windows = 0;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Прогон `windows` окон усреднения с частотой кадров `fps`.
+    /// Возвращает последнее изменение уровня.
+    fn run(quality: &mut Quality, fps: f32, windows: u32) -> Option<QualityLevel> {
+        // Длительность кадра точно представима, чтобы окна не сбивались из-за округления.
+        const FRAME_TIME: f32 = 0.25;
+        let frames = windows * (Quality::WINDOW / FRAME_TIME) as u32;
+        (0..frames).fold(None, |last, _| quality.update(fps, FRAME_TIME).or(last))
+    }
+
+    #[test]
+    fn lowers_quickly_and_raises_with_hysteresis() {
+        let mut quality = Quality::default();
+        assert_eq!(run(&mut quality, 60.0, 5), None);
+
+        // Одно медленное окно понижает качество.
+        assert_eq!(run(&mut quality, 40.0, 1), Some(LEVELS[1]));
+        // Частота между порогами ничего не меняет.
+        assert_eq!(run(&mut quality, 55.0, 10), None);
+        // Качество возвращается только после нескольких быстрых окон подряд.
+        assert_eq!(run(&mut quality, 60.0, 2), None);
+        assert_eq!(run(&mut quality, 60.0, 1), Some(LEVELS[0]));
+        // Ниже низшего уровня качество не опускается.
+        run(&mut quality, 10.0, 5);
+        assert_eq!(quality.level(), LEVELS[2]);
+    }
+}
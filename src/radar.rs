@@ -0,0 +1,90 @@
+//! Радар в углу экрана, показывающий астероиды вокруг корабля, в том числе за краями экрана.
+
+use crate::theme::Theme;
+use macroquad::prelude::*;
+
+/// Радар. По умолчанию показан, переключается клавишей M.
+pub struct Radar {
+    /// Показан ли радар.
+    visible: bool,
+}
+
+impl Default for Radar {
+    fn default() -> Self {
+        Self { visible: true }
+    }
+}
+
+impl Radar {
+    // Размеры радара на экране.
+    const WIDTH: f32 = 160.0;
+    const HEIGHT: f32 = 120.0;
+    const MARGIN: f32 = 10.0;
+    /// Отступ от нижнего края, оставляющий место для полос запаса под радаром.
+    const BOTTOM_OFFSET: f32 = 100.0;
+    /// Радар охватывает по обе стороны от корабля столько размеров окна.
+    const RANGE: f32 = 1.0;
+    /// Наибольшее число отмечаемых астероидов, чтобы радар не замедлял отображение.
+    const MAX_DOTS: usize = 64;
+    /// Наименьший радиус отметки астероида, чтобы мелкие астероиды были заметны.
+    const MIN_DOT_RADIUS: f32 = 1.5;
+
+    /// Переключение радара.
+    pub fn update(&mut self) {
+        if is_key_pressed(KeyCode::M) {
+            self.visible = !self.visible;
+        }
+    }
+
+    /// Отображение радара в правом нижнем углу над полосами запаса, если он включён.
+    ///
+    /// Положения астероидов (центры и радиусы из `asteroids`) отсчитываются от центра
+    /// корабля `ship` и уменьшаются так, что радар вмещает `RANGE` размеров окна
+    /// `screen_size` в каждую сторону. Видимая часть окна обводится рамкой.
+    pub fn draw(
+        &self,
+        ship: Vec2,
+        asteroids: impl Iterator<Item = (Vec2, f32)>,
+        screen_size: Vec2,
+        theme: &Theme,
+    ) {
+        if !self.visible {
+            return;
+        }
+
+        let size = Vec2::new(Self::WIDTH, Self::HEIGHT);
+        let corner = Vec2::new(
+            screen_width() - Self::MARGIN - Self::WIDTH,
+            screen_height() - Self::BOTTOM_OFFSET - Self::HEIGHT,
+        );
+        let center = corner + size / 2.0;
+        let scale = size / (2.0 * Self::RANGE * screen_size);
+        // Перевод точки игрового мира в точку радара.
+        let to_radar = |point: Vec2| center + (point - ship) * scale;
+
+        let mut background = theme.background;
+        background.a = 0.7;
+        draw_rectangle(corner.x, corner.y, size.x, size.y, background);
+
+        // Видимая часть окна.
+        let (screen_min, screen_max) = (to_radar(Vec2::ZERO), to_radar(screen_size));
+        let screen = screen_max - screen_min;
+        let mut frame = theme.text;
+        frame.a = 0.3;
+        draw_rectangle_lines(screen_min.x, screen_min.y, screen.x, screen.y, 1.0, frame);
+
+        // Астероиды дальше, чем охватывает радар, пропускаем.
+        let (min, max) = (corner, corner + size);
+        let dots = asteroids
+            .map(|(position, radius)| (to_radar(position), radius))
+            .filter(|(dot, _)| dot.cmpge(min).all() && dot.cmple(max).all())
+            .take(Self::MAX_DOTS);
+        for (dot, radius) in dots {
+            let radius = (radius * scale.x).max(Self::MIN_DOT_RADIUS);
+            draw_circle(dot.x, dot.y, radius, theme.asteroid_outline);
+        }
+
+        draw_circle(center.x, center.y, 2.5, theme.ship);
+        draw_rectangle_lines(corner.x, corner.y, size.x, size.y, 2.0, theme.text);
+    }
+}
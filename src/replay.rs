@@ -0,0 +1,378 @@
+//! Запись и воспроизведение игр.
+//!
+//! Игра детерминирована при одинаковом зерне генератора случайных чисел, поэтому
+//! для её повторения достаточно сохранить зерно, настройки игры (режим управления, края экрана,
+//! уровень сложности, волны, движение по вертикали) и управление кораблём вместе с длительностью каждого кадра. Положения
+//! астероидов зависят от размера окна, поэтому запись точно повторяется при том же размере окна.
+//! Запись хранится в компактном двоичном виде, описанном в [`Replay::save`].
+
+use crate::difficulty::Difficulty;
+use crate::input::Input;
+use crate::ship::FlightMode;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::storage;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Файл, в который сохраняется последняя сыгранная игра (см. [`storage`]).
+#[cfg(not(target_arch = "wasm32"))]
+const REPLAY_FILE: &str = "replay.bin";
+
+/// Управление кораблём в одном кадре.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct FrameInput {
+    /// Управление кораблём.
+    pub input: Input,
+    /// Время, прошедшее с предыдущего кадра.
+    pub elapsed_time: f64,
+}
+
+/// Запись игры.
+#[derive(Serialize, Deserialize)]
+pub struct Replay {
+    /// Зерно генератора случайных чисел.
+    pub seed: u64,
+    /// Режим управления кораблём.
+    pub flight_mode: FlightMode,
+    /// Переносится ли корабль через края окна.
+    pub wrap_edges: bool,
+    /// Уровень сложности.
+    pub difficulty: Difficulty,
+    /// Появляются ли астероиды волнами.
+    pub waves: bool,
+    /// Может ли корабль в классическом режиме двигаться по вертикали.
+    pub vertical_movement: bool,
+    /// День ежедневного испытания (в виде числа `ГГГГММДД`), если это оно.
+    pub daily: Option<u32>,
+    /// Время, которое нужно продержаться, если это режим на время.
+    pub goal: Option<f64>,
+    /// Управление в каждом кадре.
+    pub frames: Vec<FrameInput>,
+}
+
+impl Replay {
+    /// Создание пустой записи игры.
+    pub fn new(
+        seed: u64,
+        flight_mode: FlightMode,
+        wrap_edges: bool,
+        difficulty: Difficulty,
+        waves: bool,
+        vertical_movement: bool,
+    ) -> Self {
+        Self {
+            seed,
+            flight_mode,
+            wrap_edges,
+            difficulty,
+            waves,
+            vertical_movement,
+            daily: None,
+            goal: None,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Сохранение записи в файл. Ошибка записи не прерывает игру.
+    ///
+    /// Файл начинается с заголовка: сигнатуры `ASTR`, версии формата, зерна, флагов
+    /// настроек (см. `FLAG_*`) и уровня сложности. За ними для ежедневного испытания
+    /// следует его день, а для режима на время - цель, и затем число кадров.
+    /// Каждый кадр - длительность и байт с нажатыми кнопками и видом отклонения
+    /// (см. `INPUT_*`), а за ним, если нужно, отклонение стика и положение курсора мыши.
+    /// Числа записываются в порядке от младшего байта к старшему.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self) {
+        if let Err(err) = storage::write_bytes(REPLAY_FILE, &self.to_bytes()) {
+            warn!("Failed to save replay: {}", err);
+        }
+    }
+
+    /// Загрузка записи последней игры. Если файла нет или он повреждён, то записи нет.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Option<Self> {
+        let replay = Self::from_bytes(&storage::read_bytes(REPLAY_FILE)?);
+        if replay.is_none() {
+            warn!("Failed to parse replay file {}", REPLAY_FILE);
+        }
+        replay
+    }
+
+    /// Загрузка записи из файла `path`. Если файла нет или он повреждён, то записи нет.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_from(path: &str) -> Option<Self> {
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                warn!("Failed to read replay file {}: {}", path, err);
+                return None;
+            }
+        };
+        let replay = Self::from_bytes(&bytes);
+        if replay.is_none() {
+            warn!("Failed to parse replay file {}", path);
+        }
+        replay
+    }
+
+    /// Запись в двоичном формате, описанном в [`Replay::save`].
+    #[cfg(not(target_arch = "wasm32"))]
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        let flags = [
+            (self.flight_mode == FlightMode::Free, FLAG_FREE_FLIGHT),
+            (self.wrap_edges, FLAG_WRAP_EDGES),
+            (self.waves, FLAG_WAVES),
+            (self.vertical_movement, FLAG_VERTICAL),
+            (self.daily.is_some(), FLAG_DAILY),
+            (self.goal.is_some(), FLAG_GOAL),
+        ];
+        bytes.push(pack(flags));
+        bytes.push(self.difficulty.index() as u8);
+        if let Some(date) = self.daily {
+            bytes.extend_from_slice(&date.to_le_bytes());
+        }
+        if let Some(goal) = self.goal {
+            bytes.extend_from_slice(&goal.to_le_bytes());
+        }
+
+        bytes.extend_from_slice(&(self.frames.len() as u32).to_le_bytes());
+        for frame in &self.frames {
+            let input = frame.input;
+            bytes.extend_from_slice(&frame.elapsed_time.to_le_bytes());
+            // Клавиатура отклоняет корабль полностью, поэтому отклонение обычно умещается
+            // в два бита, и только отклонение стика записывается числом.
+            let steer = match input.steer {
+                0.0 => INPUT_STEER_NONE,
+                -1.0 => INPUT_STEER_LEFT,
+                1.0 => INPUT_STEER_RIGHT,
+                _ => INPUT_STEER_ANALOG,
+            };
+            let buttons = [
+                (input.thrust, INPUT_THRUST),
+                (input.descend, INPUT_DESCEND),
+                (input.fire, INPUT_FIRE),
+                (input.slow, INPUT_SLOW),
+                (input.bomb, INPUT_BOMB),
+                (input.target.is_some(), INPUT_TARGET),
+            ];
+            bytes.push(pack(buttons) | steer);
+            if steer == INPUT_STEER_ANALOG {
+                bytes.extend_from_slice(&input.steer.to_le_bytes());
+            }
+            if let Some(target) = input.target {
+                bytes.extend_from_slice(&target.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Разбор записи в двоичном формате, описанном в [`Replay::save`].
+    /// Если формат другой или запись оборвана, то записи нет.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader { bytes };
+        if reader.take::<4>()? != *MAGIC || reader.u8()? != VERSION {
+            return None;
+        }
+        let seed = u64::from_le_bytes(reader.take()?);
+        let flags = reader.u8()?;
+        let flight_mode = if flags & FLAG_FREE_FLIGHT != 0 {
+            FlightMode::Free
+        } else {
+            FlightMode::Classic
+        };
+        let difficulty = *Difficulty::ALL.get(reader.u8()? as usize)?;
+        let mut replay = Self::new(
+            seed,
+            flight_mode,
+            flags & FLAG_WRAP_EDGES != 0,
+            difficulty,
+            flags & FLAG_WAVES != 0,
+            flags & FLAG_VERTICAL != 0,
+        );
+        if flags & FLAG_DAILY != 0 {
+            replay.daily = Some(u32::from_le_bytes(reader.take()?));
+        }
+        if flags & FLAG_GOAL != 0 {
+            replay.goal = Some(f64::from_le_bytes(reader.take()?));
+        }
+
+        let count = u32::from_le_bytes(reader.take()?);
+        for _ in 0..count {
+            let elapsed_time = f64::from_le_bytes(reader.take()?);
+            let buttons = reader.u8()?;
+            let steer = match buttons & INPUT_STEER_MASK {
+                INPUT_STEER_NONE => 0.0,
+                INPUT_STEER_LEFT => -1.0,
+                INPUT_STEER_RIGHT => 1.0,
+                _ => f32::from_le_bytes(reader.take()?),
+            };
+            let target = match buttons & INPUT_TARGET {
+                0 => None,
+                _ => Some(f32::from_le_bytes(reader.take()?)),
+            };
+            replay.frames.push(FrameInput {
+                input: Input {
+                    steer,
+                    thrust: buttons & INPUT_THRUST != 0,
+                    descend: buttons & INPUT_DESCEND != 0,
+                    fire: buttons & INPUT_FIRE != 0,
+                    slow: buttons & INPUT_SLOW != 0,
+                    bomb: buttons & INPUT_BOMB != 0,
+                    target,
+                },
+                elapsed_time,
+            });
+        }
+        // Лишние байты в конце означают, что файл повреждён.
+        reader.bytes.is_empty().then_some(replay)
+    }
+
+    /// В браузере файловой системы нет, поэтому записи не сохраняются.
+    #[cfg(target_arch = "wasm32")]
+    pub fn save(&self) {}
+
+    /// В браузере файловой системы нет, поэтому записей нет.
+    #[cfg(target_arch = "wasm32")]
+    pub fn load() -> Option<Self> {
+        None
+    }
+}
+
+/// Сигнатура в начале файла записи.
+#[cfg(not(target_arch = "wasm32"))]
+const MAGIC: &[u8; 4] = b"ASTR";
+/// Версия формата записи. Меняется при любом изменении формата: записи другой версии
+/// не загружаются.
+#[cfg(not(target_arch = "wasm32"))]
+const VERSION: u8 = 1;
+
+// Флаги настроек в заголовке записи.
+#[cfg(not(target_arch = "wasm32"))]
+const FLAG_FREE_FLIGHT: u8 = 1 << 0;
+#[cfg(not(target_arch = "wasm32"))]
+const FLAG_WRAP_EDGES: u8 = 1 << 1;
+#[cfg(not(target_arch = "wasm32"))]
+const FLAG_WAVES: u8 = 1 << 2;
+#[cfg(not(target_arch = "wasm32"))]
+const FLAG_VERTICAL: u8 = 1 << 3;
+#[cfg(not(target_arch = "wasm32"))]
+const FLAG_DAILY: u8 = 1 << 4;
+#[cfg(not(target_arch = "wasm32"))]
+const FLAG_GOAL: u8 = 1 << 5;
+
+// Байт управления в кадре: нажатые кнопки, наличие курсора мыши
+// и в двух старших битах - вид отклонения.
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_THRUST: u8 = 1 << 0;
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_DESCEND: u8 = 1 << 1;
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_FIRE: u8 = 1 << 2;
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_SLOW: u8 = 1 << 3;
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_BOMB: u8 = 1 << 4;
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_TARGET: u8 = 1 << 5;
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_STEER_MASK: u8 = 0b11 << 6;
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_STEER_NONE: u8 = 0 << 6;
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_STEER_LEFT: u8 = 1 << 6;
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_STEER_RIGHT: u8 = 2 << 6;
+/// Отклонение стика: за байтом управления следует число.
+#[cfg(not(target_arch = "wasm32"))]
+const INPUT_STEER_ANALOG: u8 = 3 << 6;
+
+/// Упаковка флагов: каждый установленный флаг добавляет свой бит.
+#[cfg(not(target_arch = "wasm32"))]
+fn pack<const N: usize>(flags: [(bool, u8); N]) -> u8 {
+    flags
+        .into_iter()
+        .filter(|&(set, _)| set)
+        .fold(0, |byte, (_, bit)| byte | bit)
+}
+
+/// Последовательное чтение байтов записи.
+#[cfg(not(target_arch = "wasm32"))]
+struct Reader<'a> {
+    /// Ещё не прочитанные байты.
+    bytes: &'a [u8],
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Reader<'_> {
+    /// Чтение следующих `N` байтов. Если их не осталось, то ничего.
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, tail) = self.bytes.split_first_chunk()?;
+        self.bytes = tail;
+        Some(*head)
+    }
+
+    /// Чтение одного байта.
+    fn u8(&mut self) -> Option<u8> {
+        self.take::<1>().map(|[byte]| byte)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_format_round_trips() {
+        let mut replay = Replay::new(42, FlightMode::Free, true, Difficulty::Hard, false, true);
+        replay.goal = Some(60.0);
+        let frames = [
+            Input::default(),
+            Input {
+                steer: -1.0,
+                fire: true,
+                ..Input::default()
+            },
+            Input {
+                steer: 0.37,
+                thrust: true,
+                bomb: true,
+                target: Some(123.5),
+                ..Input::default()
+            },
+        ];
+        for (index, input) in frames.into_iter().enumerate() {
+            let elapsed_time = 1.0 / (60.0 + index as f64);
+            replay.frames.push(FrameInput {
+                input,
+                elapsed_time,
+            });
+        }
+
+        let bytes = replay.to_bytes();
+        let loaded = Replay::from_bytes(&bytes).expect("replay is valid");
+        assert_eq!(loaded.seed, 42);
+        assert!(loaded.flight_mode == FlightMode::Free);
+        assert!(loaded.wrap_edges && !loaded.waves && loaded.vertical_movement);
+        assert!(loaded.difficulty == Difficulty::Hard);
+        assert_eq!(loaded.daily, None);
+        assert_eq!(loaded.goal, Some(60.0));
+        assert_eq!(loaded.frames.len(), 3);
+        for (loaded, saved) in loaded.frames.iter().zip(&replay.frames) {
+            // Длительность кадров сохраняется точно: от неё зависит повторение игры.
+            assert_eq!(loaded.elapsed_time, saved.elapsed_time);
+            assert_eq!(loaded.input.steer, saved.input.steer);
+            assert_eq!(loaded.input.target, saved.input.target);
+            assert_eq!(loaded.input.thrust, saved.input.thrust);
+            assert_eq!(loaded.input.fire, saved.input.fire);
+            assert_eq!(loaded.input.bomb, saved.input.bomb);
+        }
+
+        // Оборванная запись не загружается.
+        assert!(Replay::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+    }
+}
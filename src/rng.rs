@@ -0,0 +1,40 @@
+//! Генератор псевдослучайных чисел с явным зерном.
+
+use serde::{Deserialize, Serialize};
+
+/// Генератор псевдослучайных чисел (xorshift64*).
+///
+/// В отличие от глобального генератора macroquad, каждая игра владеет своим
+/// экземпляром, поэтому при одинаковом зерне астероиды появляются одинаково.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Создание генератора из зерна `seed`.
+    pub fn new(seed: u64) -> Self {
+        // Перемешиваем зерно (splitmix64), чтобы близкие зёрна давали непохожие
+        // последовательности, а нулевое зерно не останавливало xorshift.
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        Self { state: z.max(1) }
+    }
+
+    /// Следующее случайное 64-битное число.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Случайное число в полуинтервале [`low`, `high`).
+    pub fn gen_range(&mut self, low: f32, high: f32) -> f32 {
+        // Старшие 24 бита точно представимы в f32.
+        let unit = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        low + (high - low) * unit
+    }
+}
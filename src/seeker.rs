@@ -0,0 +1,98 @@
+//! Самонаводящийся враг, преследующий корабль.
+
+use crate::rng::Rng;
+use crate::theme::Theme;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Самонаводящийся враг. Летит к текущему положению корабля, но поворачивает
+/// с ограниченной скоростью, так что от него можно увернуться резким манёвром.
+/// Через некоторое время исчезает сам.
+#[derive(Serialize, Deserialize)]
+pub struct Seeker {
+    position: Vec2,
+    /// Положение перед последним шагом игрового мира, для плавного отображения между шагами.
+    prev_position: Vec2,
+    /// Единичный вектор направления полёта.
+    direction: Vec2,
+    /// Время с появления в секундах.
+    age: f32,
+}
+
+impl Seeker {
+    // Параметры преследователя.
+    pub const RADIUS: f32 = 14.0;
+    const SPEED: f32 = 220.0;
+    /// Наибольшая скорость поворота в радианах в секунду.
+    const TURN_RATE: f32 = 1.6;
+    /// Время жизни в секундах.
+    const LIFETIME: f32 = 12.0;
+
+    /// Создание преследователя в случайном месте над экраном шириной `width`.
+    /// Сначала он летит вниз.
+    pub fn new(rng: &mut Rng, width: f32) -> Self {
+        let position = Vec2::new(rng.gen_range(0.0, width), -Self::RADIUS);
+        Self {
+            position,
+            prev_position: position,
+            direction: Vec2::new(0.0, 1.0),
+            age: 0.0,
+        }
+    }
+
+    /// Обновление состояния: поворот к цели `target`, но не быстрее `TURN_RATE`, и полёт вперёд.
+    pub fn update(&mut self, elapsed_time: f64, target: Vec2) {
+        let elapsed_time = elapsed_time as f32;
+        self.prev_position = self.position;
+        self.age += elapsed_time;
+
+        let desired = (target - self.position).normalize_or_zero();
+        if desired != Vec2::ZERO {
+            // Угол со знаком от текущего направления до направления на цель.
+            let angle = self
+                .direction
+                .perp_dot(desired)
+                .atan2(self.direction.dot(desired));
+            let max_turn = Self::TURN_RATE * elapsed_time;
+            let turn = angle.clamp(-max_turn, max_turn);
+            self.direction = (Mat2::from_angle(turn) * self.direction).normalize();
+        }
+        self.position += self.direction * Self::SPEED * elapsed_time;
+    }
+
+    /// Не истекло ли время жизни.
+    pub fn is_alive(&self) -> bool {
+        self.age < Self::LIFETIME
+    }
+
+    /// Перенос в окно изменившегося размера.
+    pub fn rescale(&mut self, scale: Vec2) {
+        self.position *= scale;
+        self.prev_position *= scale;
+    }
+
+    /// Положение центра.
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Отображение красным ромбом с указателем направления полёта, а если объекты
+    /// различаются по форме (см. [`Theme::shapes`]) - треугольником.
+    /// `alpha` - доля пути от предыдущего положения к текущему.
+    pub fn draw(&self, theme: &Theme, alpha: f32) {
+        let position = self.prev_position.lerp(self.position, alpha);
+        let forward = self.direction * Self::RADIUS;
+        let side = forward.perp() * 0.6;
+        let (nose, tail) = (position + forward, position - forward);
+        if theme.shapes {
+            let side = side * 1.5;
+            draw_triangle(nose, tail - side, tail + side, RED);
+        } else {
+            let (left, right) = (position - side, position + side);
+            draw_triangle(nose, left, tail, RED);
+            draw_triangle(nose, right, tail, RED);
+        }
+        let pointer = position + forward * 1.6;
+        draw_line(nose.x, nose.y, pointer.x, pointer.y, 2.0, ORANGE);
+    }
+}
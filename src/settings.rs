@@ -0,0 +1,158 @@
+//! Настройки, сохраняемые между запусками игры.
+
+use crate::difficulty::Difficulty;
+use crate::input::{AxisResponse, Curve};
+use crate::lang::Lang;
+use crate::storage;
+use crate::theme::{Theme, ThemePreset};
+use macroquad::prelude::*;
+
+/// Файл с настройками.
+const SETTINGS_FILE: &str = "settings.cfg";
+
+/// Настройки, изменяемые на экране настроек.
+#[derive(Clone, Copy)]
+pub struct Settings {
+    /// Общая громкость от 0 до 1.
+    pub volume: f32,
+    /// Уровень сложности.
+    pub difficulty: Difficulty,
+    /// Язык интерфейса.
+    pub lang: Lang,
+    /// Переносятся ли края экрана на противоположную сторону в классическом режиме.
+    pub wrap_edges: bool,
+    /// Управляется ли корабль в классическом режиме мышью вместо клавиатуры.
+    pub mouse_steering: bool,
+    /// Цветовая тема.
+    pub theme: ThemePreset,
+    /// Появляются ли астероиды волнами вместо непрерывного потока.
+    pub waves: bool,
+    /// Может ли корабль в классическом режиме двигаться по вертикали.
+    pub vertical_movement: bool,
+    /// Мёртвая зона стика геймпада от 0 до `MAX_DEAD_ZONE`.
+    pub dead_zone: f32,
+    /// Кривая отклика стика геймпада.
+    pub curve: Curve,
+    /// Различаются ли объекты формой, а не только цветом.
+    pub shapes: bool,
+    /// Наибольшая частота кадров или 0, если она не ограничена.
+    pub frame_limit: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            difficulty: Difficulty::Normal,
+            lang: Lang::English,
+            wrap_edges: false,
+            mouse_steering: false,
+            theme: ThemePreset::Dark,
+            waves: false,
+            vertical_movement: false,
+            dead_zone: 0.15,
+            curve: Curve::Linear,
+            shapes: false,
+            frame_limit: 0,
+        }
+    }
+}
+
+impl Settings {
+    /// Наибольшая мёртвая зона стика: иначе на управление оставалось бы слишком мало хода.
+    pub const MAX_DEAD_ZONE: f32 = 0.5;
+    /// Варианты ограничения частоты кадров на экране настроек. 0 - без ограничения.
+    pub const FRAME_LIMITS: [u32; 5] = [0, 30, 60, 120, 144];
+
+    /// Цветовая тема с учётом различения объектов по форме.
+    pub fn theme(&self) -> Theme {
+        Theme {
+            shapes: self.shapes,
+            ..self.theme.theme()
+        }
+    }
+
+    /// Настройки аналогового управления.
+    pub fn axis_response(&self) -> AxisResponse {
+        AxisResponse {
+            dead_zone: self.dead_zone,
+            curve: self.curve,
+        }
+    }
+
+    /// Загрузка настроек из файла.
+    ///
+    /// Формат файла тот же, что и у файла с назначением клавиш: строки вида `volume = 0.5`.
+    /// Настройки, не упомянутые в файле, а также строки, которые не удалось разобрать,
+    /// остаются по умолчанию.
+    pub fn load() -> Self {
+        let mut settings = Self::default();
+        let text = match storage::read(SETTINGS_FILE) {
+            Some(text) => text,
+            None => return settings, // Файла нет - используем настройки по умолчанию.
+        };
+
+        for line in text.lines().map(str::trim) {
+            // Пропускаем пустые строки и комментарии.
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parsed = line.split_once('=').and_then(|(name, value)| {
+                let value = value.trim();
+                match name.trim() {
+                    "volume" => {
+                        let volume = value.parse::<f32>().ok()?;
+                        settings.volume = volume.clamp(0.0, 1.0);
+                    }
+                    "difficulty" => settings.difficulty = Difficulty::from_key(value)?,
+                    "lang" => settings.lang = Lang::from_key(value)?,
+                    "wrap_edges" => settings.wrap_edges = value.parse().ok()?,
+                    "mouse_steering" => settings.mouse_steering = value.parse().ok()?,
+                    "theme" => settings.theme = ThemePreset::from_key(value)?,
+                    "waves" => settings.waves = value.parse().ok()?,
+                    "vertical_movement" => settings.vertical_movement = value.parse().ok()?,
+                    "dead_zone" => {
+                        let dead_zone = value.parse::<f32>().ok()?;
+                        settings.dead_zone = dead_zone.clamp(0.0, Self::MAX_DEAD_ZONE);
+                    }
+                    "curve" => settings.curve = Curve::from_key(value)?,
+                    "shapes" => settings.shapes = value.parse().ok()?,
+                    "frame_limit" => settings.frame_limit = value.parse().ok()?,
+                    _ => return None,
+                }
+                Some(())
+            });
+
+            if parsed.is_none() {
+                warn!("Failed to parse setting: {}", line);
+            }
+        }
+
+        settings
+    }
+
+    /// Сохранение настроек в файл. Ошибка записи не прерывает игру.
+    pub fn save(&self) {
+        let text = format!(
+            "volume = {}\ndifficulty = {}\nlang = {}\nwrap_edges = {}\nmouse_steering = {}\n\
+             theme = {}\nwaves = {}\nvertical_movement = {}\ndead_zone = {}\ncurve = {}\n\
+             shapes = {}\nframe_limit = {}\n",
+            self.volume,
+            self.difficulty.key(),
+            self.lang.key(),
+            self.wrap_edges,
+            self.mouse_steering,
+            self.theme.key(),
+            self.waves,
+            self.vertical_movement,
+            self.dead_zone,
+            self.curve.key(),
+            self.shapes,
+            self.frame_limit
+        );
+        if let Err(err) = storage::write(SETTINGS_FILE, &text) {
+            warn!("Failed to save settings: {}", err);
+        }
+    }
+}
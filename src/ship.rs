@@ -0,0 +1,904 @@
+//! Корабль игрока.
+
+use crate::assets;
+use crate::bullet::Bullet;
+use crate::config::GameConfig;
+use crate::input::Input;
+use crate::lang::TextKey;
+use crate::theme::Theme;
+use crate::weapons::Weapons;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Режим управления кораблём.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlightMode {
+    /// Классический режим: корабль скользит влево-вправо вдоль нижнего края окна,
+    /// а если включено движение по вертикали - ещё и вверх-вниз в полосе у нижнего края.
+    Classic,
+    /// Свободный полёт: A/D поворачивают корабль, W разгоняет его по направлению носа
+    /// (в классическом режиме W включает форсаж),
+    /// а края окна замкнуты друг на друга.
+    Free,
+}
+
+impl FlightMode {
+    /// Следующий режим управления (для переключения в меню).
+    pub fn next(self) -> Self {
+        match self {
+            FlightMode::Classic => FlightMode::Free,
+            FlightMode::Free => FlightMode::Classic,
+        }
+    }
+
+    /// Название режима для отображения.
+    pub fn name(self) -> TextKey {
+        match self {
+            FlightMode::Classic => TextKey::ModeClassic,
+            FlightMode::Free => TextKey::ModeFree,
+        }
+    }
+}
+
+/// Состояние корабля.
+#[derive(Serialize, Deserialize)]
+pub struct Ship {
+    /// Режим управления.
+    mode: FlightMode,
+    /// Параметры движения.
+    config: GameConfig,
+    /// Переносится ли корабль через края окна в классическом режиме.
+    /// В свободном полёте корабль переносится всегда.
+    wrap_edges: bool,
+    /// Может ли корабль в классическом режиме двигаться по вертикали
+    /// в полосе высотой `VERTICAL_RANGE` у нижнего края окна.
+    vertical_movement: bool,
+    /// Положение центра корабля (середины основания треугольника), в пикселях.
+    position: Vec2,
+    /// Положение корабля перед последним шагом игрового мира.
+    /// Между шагами корабль отображается между этим и текущим положением.
+    prev_position: Vec2,
+    /// Скорость корабля (пиксели в секунду).
+    /// В классическом режиме вертикальная составляющая используется,
+    /// только если включено движение по вертикали.
+    velocity: Vec2,
+    /// Угол поворота корабля в радианах, отсчитываемый от направления вверх по часовой стрелке.
+    /// В классическом режиме всегда равен нулю.
+    rotation: f32,
+    /// Скорость по вертикали (с которой, относительно корабля, движутся астероиды),
+    /// в пикселях в секунду.
+    vertical_speed: f32,
+    /// Сглаженная скорость по вертикали (с учётом форсажа), с которой на самом деле
+    /// движутся астероиды и всё остальное. Сглаживание убирает рывки от резких изменений
+    /// скорости, например при включении форсажа.
+    smoothed_vertical_speed: f32,
+    /// Запас форсажа в секундах.
+    boost_meter: f32,
+    /// Включён ли форсаж в последнем кадре.
+    boosting: bool,
+    /// Оставшиеся жизни.
+    lives: u32,
+    /// Оставшееся время неуязвимости после столкновения.
+    invulnerability_timer: f32,
+    /// Оставшееся время действия щита.
+    shield_timer: f32,
+    /// Сила тяги в последнем кадре от 0 до 1 (только для отображения пламени).
+    thrust: f32,
+    /// Время в секундах от 0 до 1, по которому пламя мерцает.
+    /// Отсчитывается по времени, а не по шагам игрового мира: шагов в кадре бывает
+    /// несколько, и переключение на каждом шаге было бы незаметно.
+    flicker: f32,
+    /// Недавние положения центра корабля для следа, от новых к старым.
+    /// Не длиннее `TRAIL_LENGTH`, поэтому память выделяется только при создании корабля.
+    trail: VecDeque<Vec2>,
+    /// Время с последнего добавления точки в след.
+    trail_timer: f32,
+    /// Орудие корабля.
+    weapons: Weapons,
+}
+
+impl Ship {
+    // Параметры корабля.
+    const SHIP_WIDTH: f32 = 25.0;
+    const SHIP_HEIGHT: f32 = 50.0;
+    /// Радиус круга вокруг центра корабля, вне которого столкновений нет. Сейчас круг
+    /// описывает весь треугольник; если его уменьшить, нос и углы корабля станут безопасными.
+    const COLLISION_RADIUS: f32 = (Self::SHIP_WIDTH + Self::SHIP_HEIGHT) / 4.0;
+    const SHIP_OFFSET: f32 = 30.0;
+    const LIVES: u32 = 3;
+    const INVULNERABILITY_DURATION: f32 = 2.0;
+    /// Наибольший запас форсажа в секундах.
+    const BOOST_METER_MAX: f32 = 2.0;
+    /// Скорость восстановления запаса форсажа (секунд запаса за секунду).
+    const BOOST_METER_RECHARGE: f32 = 0.4;
+    /// Наибольшая скорость по вертикали без форсажа в пикселях в секунду. Выше неё
+    /// астероиды проносились бы мимо быстрее, чем на них можно успеть отреагировать.
+    const MAX_VERTICAL_SPEED: f32 = 1000.0;
+    /// Постоянная времени сглаживания скорости по вертикали в секундах: за это время
+    /// сглаженная скорость проходит около двух третей пути до настоящей.
+    const SPEED_SMOOTHING_TIME: f32 = 0.15;
+    /// Скорость, добавляемая к скорости по вертикали на время форсажа, в пикселях в секунду.
+    const BOOST_SPEED: f32 = 400.0;
+    /// Высота полосы у нижнего края, в которой корабль движется по вертикали,
+    /// в долях высоты окна.
+    const VERTICAL_RANGE: f32 = 0.35;
+    /// Ускорение корабля по вертикали в классическом режиме в пикселях в секунду за секунду.
+    const VERTICAL_ACCELERATION: f32 = 1500.0;
+    /// Доля скорости по вертикали, теряемая кораблём за секунду в классическом режиме.
+    const VERTICAL_DECELERATION: f32 = 6.0;
+    /// Число точек следа.
+    const TRAIL_LENGTH: usize = 10;
+    /// Интервал между точками следа в секундах.
+    const TRAIL_INTERVAL: f32 = 0.025;
+    /// Скорость относительно астероидов, при которой след наиболее заметен.
+    const TRAIL_FULL_SPEED: f32 = 700.0;
+
+    /// Создание корабля с заданным режимом управления в окне размером `screen_size`.
+    /// Если `wrap_edges` установлен, то в классическом режиме корабль переносится
+    /// с одного края окна на другой. Ускорения корабля задаются настройками `config`.
+    pub fn new(mode: FlightMode, wrap_edges: bool, config: GameConfig, screen_size: Vec2) -> Self {
+        // Изначально корабль находится по центру окна у нижнего края.
+        let position = Vec2::new(screen_size.x / 2.0, screen_size.y - Self::SHIP_OFFSET);
+        Self {
+            mode,
+            config,
+            wrap_edges,
+            vertical_movement: false,
+            position,
+            prev_position: position,
+            velocity: Vec2::ZERO,
+            rotation: 0.0,
+            vertical_speed: 100.0,
+            smoothed_vertical_speed: 100.0,
+            boost_meter: Self::BOOST_METER_MAX,
+            boosting: false,
+            lives: Self::LIVES,
+            invulnerability_timer: 0.0,
+            shield_timer: 0.0,
+            thrust: 0.0,
+            flicker: 0.0,
+            trail: VecDeque::with_capacity(Self::TRAIL_LENGTH + 1),
+            trail_timer: 0.0,
+            weapons: Weapons::default(),
+        }
+    }
+
+    /// Логика обновления корабля в окне размером `screen_size`.
+    pub fn update(&mut self, input: Input, elapsed_time: f64, screen_size: Vec2) {
+        let elapsed_time = elapsed_time as f32;
+        self.prev_position = self.position;
+
+        match self.mode {
+            FlightMode::Classic => {
+                self.update_classic(elapsed_time, input.steer, input.target, screen_size);
+                if self.vertical_movement {
+                    let lift = input.thrust as i32 - input.descend as i32;
+                    self.update_vertical(elapsed_time, lift as f32, screen_size.y);
+                }
+            }
+            FlightMode::Free => {
+                self.update_free(elapsed_time, input.steer, input.thrust, screen_size)
+            }
+        }
+        // В свободном полёте тяга разгоняет сам корабль, поэтому форсаж есть
+        // только в классическом режиме, и то если клавиша тяги не поднимает корабль.
+        self.update_boost(
+            elapsed_time,
+            self.mode == FlightMode::Classic && !self.vertical_movement && input.thrust,
+        );
+
+        // Запоминаем силу тяги для отображения пламени: в классическом режиме корабль
+        // разгоняется отклонением, а в свободном полёте - тягой.
+        // При управлении мышью силу тяги оцениваем по доле от предельной скорости.
+        self.thrust = match self.mode {
+            FlightMode::Classic if input.target.is_some() => {
+                let max_speed = self.config.ship_acceleration / self.config.ship_deceleration;
+                (self.velocity.x.abs() / max_speed).min(1.0)
+            }
+            FlightMode::Classic if self.boosting => 1.0,
+            FlightMode::Classic => input.steer.abs(),
+            FlightMode::Free if input.thrust => 1.0,
+            FlightMode::Free => 0.0,
+        };
+        self.flicker = (self.flicker + elapsed_time) % 1.0;
+
+        // Ускоряем корабль по вертикали, пока скорость не достигнет предела.
+        let vertical_speed = self.vertical_speed + self.config.vertical_acceleration * elapsed_time;
+        self.vertical_speed = vertical_speed.min(Self::MAX_VERTICAL_SPEED);
+        // Сглаженная скорость приближается к настоящей экспоненциально.
+        let smoothing = 1.0 - (-elapsed_time / Self::SPEED_SMOOTHING_TIME).exp();
+        self.smoothed_vertical_speed +=
+            (self.vertical_speed() - self.smoothed_vertical_speed) * smoothing;
+
+        self.update_trail(elapsed_time);
+
+        // Отсчитываем время неуязвимости.
+        self.invulnerability_timer = (self.invulnerability_timer - elapsed_time).max(0.0);
+        self.shield_timer = (self.shield_timer - elapsed_time).max(0.0);
+    }
+
+    /// Разрешение кораблю двигаться по вертикали в классическом режиме.
+    /// Клавиша тяги тогда поднимает корабль вместо форсажа, а клавиша вниз опускает его.
+    pub fn allow_vertical_movement(&mut self) {
+        self.vertical_movement = true;
+    }
+
+    /// Разгон корабля внешней силой с ускорением `acceleration` в течение `elapsed_time`.
+    /// В классическом режиме корабль движется под действием внешних сил только по горизонтали.
+    pub fn accelerate(&mut self, acceleration: Vec2, elapsed_time: f64) {
+        let delta = acceleration * elapsed_time as f32;
+        match self.mode {
+            FlightMode::Classic => self.velocity.x += delta.x,
+            FlightMode::Free => self.velocity += delta,
+        }
+    }
+
+    /// Перенос корабля в окно изменившегося размера `screen_size`.
+    /// Корабль сохраняет своё положение относительно краёв окна, поэтому не перескакивает
+    /// и не оказывается за его пределами.
+    pub fn rescale(&mut self, scale: Vec2, screen_size: Vec2) {
+        self.position *= scale;
+        if self.mode == FlightMode::Classic {
+            // В классическом режиме корабль всегда находится у нижнего края
+            // или в полосе движения по вертикали.
+            let (top, bottom) = Self::vertical_band(screen_size.y);
+            self.position.y = if self.vertical_movement {
+                self.position.y.clamp(top, bottom)
+            } else {
+                bottom
+            };
+        }
+        self.prev_position = self.position;
+        for point in &mut self.trail {
+            *point *= scale;
+        }
+    }
+
+    /// Обновление следа. Точки следа уносятся вниз вместе с астероидами, поэтому
+    /// след тянется за кораблём, даже когда тот движется только по горизонтали.
+    fn update_trail(&mut self, elapsed_time: f32) {
+        let drift = Vec2::new(0.0, self.smoothed_vertical_speed * elapsed_time);
+        for point in &mut self.trail {
+            *point += drift;
+        }
+
+        self.trail_timer += elapsed_time;
+        if self.trail_timer >= Self::TRAIL_INTERVAL {
+            self.trail_timer = 0.0;
+            self.trail.push_front(self.center());
+            self.trail.truncate(Self::TRAIL_LENGTH);
+        }
+    }
+
+    /// Включение форсажа, если он запрошен (`requested`) и есть его запас.
+    /// Пока форсаж включён, запас тратится, а когда запас кончается, форсаж отключается сам.
+    /// В остальное время запас восстанавливается, но не больше чем до максимума.
+    fn update_boost(&mut self, elapsed_time: f32, requested: bool) {
+        self.boosting = requested && self.boost_meter > 0.0;
+        if self.boosting {
+            self.boost_meter = (self.boost_meter - elapsed_time).max(0.0);
+        } else {
+            let recharged = self.boost_meter + Self::BOOST_METER_RECHARGE * elapsed_time;
+            self.boost_meter = recharged.min(Self::BOOST_METER_MAX);
+        }
+    }
+
+    /// Движение корабля вдоль нижнего края окна: с ускорением по отклонению `steer`
+    /// или вслед за курсором мыши с координатой `target`, если она есть.
+    fn update_classic(
+        &mut self,
+        elapsed_time: f32,
+        steer: f32,
+        target: Option<f32>,
+        screen_size: Vec2,
+    ) {
+        // Скорость, с которой корабль догоняет курсор: за секунду расстояние
+        // до курсора сокращается в e^MOUSE_FOLLOW раз.
+        const MOUSE_FOLLOW: f32 = 10.0;
+
+        if let Some(target) = target {
+            // Корабль плавно приближается к курсору, а не перескакивает к нему.
+            let previous = self.position.x;
+            let follow = 1.0 - (-MOUSE_FOLLOW * elapsed_time).exp();
+            self.position.x += (target - self.position.x) * follow;
+            if elapsed_time > 0.0 {
+                self.velocity.x = (self.position.x - previous) / elapsed_time;
+            }
+        } else {
+            self.update_classic_speed(elapsed_time, steer);
+        }
+
+        if self.wrap_edges {
+            // Выйдя за край окна, корабль появляется с противоположной стороны.
+            // Предыдущее положение переносится вместе с ним, чтобы корабль
+            // не пролетал при отображении через весь экран.
+            let wrapped = self.position.x.rem_euclid(screen_size.x);
+            self.prev_position.x += wrapped - self.position.x;
+            self.position.x = wrapped;
+        } else {
+            self.clamp_to_screen(screen_size.x);
+        }
+
+        // Без движения по вертикали корабль всегда остаётся у нижнего края окна.
+        if !self.vertical_movement {
+            self.position.y = screen_size.y - Self::SHIP_OFFSET;
+        }
+    }
+
+    /// Движение корабля по вертикали в полосе у нижнего края окна высотой `height`
+    /// с ускорением по отклонению `lift` от -1 (вниз) до 1 (вверх).
+    fn update_vertical(&mut self, elapsed_time: f32, lift: f32, height: f32) {
+        self.velocity.y *= (1.0 - Self::VERTICAL_DECELERATION * elapsed_time).max(0.0);
+        self.velocity.y -= Self::VERTICAL_ACCELERATION * lift * elapsed_time;
+        self.position.y += self.velocity.y * elapsed_time;
+
+        // Упёршись в границу полосы, корабль останавливается.
+        let (top, bottom) = Self::vertical_band(height);
+        let clamped = self.position.y.clamp(top, bottom);
+        if clamped != self.position.y {
+            self.position.y = clamped;
+            self.velocity.y = 0.0;
+        }
+    }
+
+    /// Верхняя и нижняя границы положения корабля в классическом режиме в окне высотой `height`.
+    fn vertical_band(height: f32) -> (f32, f32) {
+        let bottom = height - Self::SHIP_OFFSET;
+        (bottom - height * Self::VERTICAL_RANGE, bottom)
+    }
+
+    /// Разгон и торможение корабля по отклонению `steer` и его перемещение.
+    fn update_classic_speed(&mut self, elapsed_time: f32, steer: f32) {
+        // Скорость, ниже которой корабль считается остановившимся.
+        const REST_SPEED: f32 = 1.0;
+
+        // Замедляем корабль по горизонтали, не позволяя скорости сменить знак.
+        self.velocity.x *= (1.0 - self.config.ship_deceleration * elapsed_time).max(0.0);
+        if self.velocity.x.abs() < REST_SPEED {
+            self.velocity.x = 0.0;
+        }
+
+        // Ускоряем корабль в сторону управления пропорционально отклонению.
+        self.velocity.x += self.config.ship_acceleration * steer * elapsed_time;
+
+        // Перемещаем корабль.
+        self.position.x += self.velocity.x * elapsed_time;
+    }
+
+    /// Ограничение положения корабля пределами окна шириной `width`.
+    fn clamp_to_screen(&mut self, width: f32) {
+        let clamped = self
+            .position
+            .x
+            .clamp(Self::SHIP_WIDTH / 2.0, width - Self::SHIP_WIDTH / 2.0);
+        if clamped != self.position.x {
+            // Упёршись в край окна, корабль останавливается.
+            self.position.x = clamped;
+            self.velocity.x = 0.0;
+        }
+    }
+
+    /// Свободный полёт с поворотом и тягой.
+    fn update_free(&mut self, elapsed_time: f32, steer: f32, thrust: bool, screen_size: Vec2) {
+        // Управление влево и вправо поворачивает корабль.
+        self.rotation += self.config.rotation_speed * steer * elapsed_time;
+
+        // Тяга разгоняет корабль по направлению носа.
+        if thrust {
+            self.velocity += self.heading() * self.config.thrust * elapsed_time;
+        }
+
+        // Замедляем корабль и перемещаем его.
+        self.velocity *= (1.0 - self.config.drag * elapsed_time).max(0.0);
+        self.position += self.velocity * elapsed_time;
+
+        // Вылетев за край окна, корабль появляется с противоположной стороны
+        // вместе с предыдущим положением.
+        let wrapped = Vec2::new(
+            self.position.x.rem_euclid(screen_size.x),
+            self.position.y.rem_euclid(screen_size.y),
+        );
+        self.prev_position += wrapped - self.position;
+        self.position = wrapped;
+    }
+
+    /// Отображаем корабль в цветах темы `theme`.
+    /// `alpha` - доля шага игрового мира, прошедшая после последнего шага: корабль
+    /// отображается на этой доле пути от предыдущего положения к текущему.
+    pub fn draw(&self, theme: &Theme, alpha: f32) {
+        self.draw_trail(theme);
+
+        let vertices = self.vertices_at(self.prev_position.lerp(self.position, alpha));
+
+        // Щит отображаем полупрозрачным кругом вокруг корабля.
+        if self.shield_time() > 0.0 {
+            let center = vertices.iter().fold(Vec2::ZERO, |sum, &v| sum + v) / 3.0;
+            let color = Color::new(0.4, 0.75, 1.0, 0.4);
+            draw_circle(center.x, center.y, Self::SHIP_HEIGHT * 0.6, color);
+        }
+
+        // Корабль, неуязвимый после столкновения, мигает:
+        // скрываем его каждую нечётную десятую долю секунды.
+        const BLINKS_PER_SECOND: f32 = 10.0;
+        if self.invulnerability_timer > 0.0
+            && (self.invulnerability_timer * BLINKS_PER_SECOND) as u32 % 2 == 1
+        {
+            return;
+        }
+
+        // Отображаем текстуру корабля, а если её нет - треугольник.
+        let [top, left, right] = vertices;
+        match assets::textures().ship {
+            Some(texture) => {
+                // Текстура занимает описанный вокруг треугольника прямоугольник.
+                let center = (top + (left + right) / 2.0) / 2.0;
+                let size = Vec2::new(Self::SHIP_WIDTH, Self::SHIP_HEIGHT);
+                assets::draw_centered(texture, center, size, self.rotation, WHITE);
+            }
+            None => draw_triangle(top, right, left, theme.ship),
+        }
+
+        self.draw_flame(vertices);
+    }
+
+    /// Отображение следа точками, которые к хвосту становятся меньше и прозрачнее.
+    /// Чем быстрее корабль летит относительно астероидов, тем след заметнее.
+    fn draw_trail(&self, theme: &Theme) {
+        const MAX_RADIUS: f32 = 6.0;
+        const MAX_OPACITY: f32 = 0.5;
+        let speed = Vec2::new(
+            self.velocity.x,
+            self.velocity.y - self.smoothed_vertical_speed,
+        );
+        let speed = speed.length();
+        let strength = (speed / Self::TRAIL_FULL_SPEED).min(1.0);
+        if strength <= 0.0 {
+            return;
+        }
+
+        let count = self.trail.len() as f32;
+        for (index, point) in self.trail.iter().enumerate() {
+            // Доля от 1 у самой новой точки почти до 0 у самой старой.
+            let fade = 1.0 - index as f32 / count;
+            let mut color = theme.ship;
+            color.a = MAX_OPACITY * fade * strength;
+            draw_circle(point.x, point.y, MAX_RADIUS * fade, color);
+        }
+    }
+
+    /// Отображение пламени двигателя за основанием корабля с вершинами `vertices`.
+    /// Длина пламени пропорциональна силе тяги, а цвет меняется каждый кадр.
+    fn draw_flame(&self, vertices: [Vec2; 3]) {
+        const MAX_LENGTH: f32 = 20.0;
+        if self.thrust <= 0.0 {
+            return;
+        }
+
+        let [_, left, right] = vertices;
+        let base = (left + right) / 2.0;
+        let tip = base - self.heading() * MAX_LENGTH * self.thrust;
+        // Пламя чуть уже основания корабля.
+        let (left, right) = (base + (left - base) * 0.6, base + (right - base) * 0.6);
+        // Пламя меняет цвет каждую нечётную тридцатую долю секунды.
+        const FLICKERS_PER_SECOND: f32 = 30.0;
+        let color = if (self.flicker * FLICKERS_PER_SECOND) as u32 % 2 == 1 {
+            YELLOW
+        } else {
+            ORANGE
+        };
+        draw_triangle(left, right, tip, color);
+    }
+
+    /// Вершины треугольника корабля: нос, левый и правый углы основания.
+    /// Используются при проверке столкновений.
+    fn vertices(&self) -> [Vec2; 3] {
+        self.vertices_at(self.position)
+    }
+
+    /// Вершины треугольника корабля, находящегося в положении `position`.
+    fn vertices_at(&self, position: Vec2) -> [Vec2; 3] {
+        Self::triangle(position, self.rotation)
+    }
+
+    /// Вершины треугольника корабля в положении `position` с поворотом `rotation`.
+    pub fn triangle(position: Vec2, rotation: f32) -> [Vec2; 3] {
+        let rotation = Mat2::from_angle(rotation);
+        let local = [
+            Vec2::new(0.0, -Self::SHIP_HEIGHT / 2.0),
+            Vec2::new(-Self::SHIP_WIDTH / 2.0, 0.0),
+            Vec2::new(Self::SHIP_WIDTH / 2.0, 0.0),
+        ];
+        // Поворачиваем вершины вокруг центра корабля и переносим в его положение.
+        local.map(|vertex| position + rotation * vertex)
+    }
+
+    /// Столкнулся ли корабль с кругом с центром в `point` и радиусом `radius`:
+    /// круг должен задеть и треугольник корабля, и круг [`Ship::collision_radius`].
+    pub fn is_collapse(&self, point: Vec2, radius: f32) -> bool {
+        let reach = self.collision_radius() + radius;
+        self.center().distance_squared(point) < reach * reach
+            && triangle_intersects_circle(self.vertices(), point, radius)
+    }
+
+    /// Радиус круга вокруг центра корабля, вне которого корабль ни с чем не сталкивается.
+    pub fn collision_radius(&self) -> f32 {
+        Self::COLLISION_RADIUS
+    }
+
+    /// Отладочное отображение опасной области: контура треугольника корабля и круга
+    /// [`Ship::collision_radius`]. `alpha` - доля пути от предыдущего положения к текущему.
+    pub fn draw_hitbox(&self, alpha: f32) {
+        let [top, left, right] = self.vertices_at(self.prev_position.lerp(self.position, alpha));
+        draw_triangle_lines(top, left, right, 1.0, RED);
+        let center = (top + left + right) / 3.0;
+        draw_circle_lines(center.x, center.y, self.collision_radius(), 1.0, YELLOW);
+    }
+
+    /// Прямоугольник (левый верхний и правый нижний углы), вне которого корабль
+    /// ни с чем не сталкивается.
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        let [a, b, c] = self.vertices();
+        (a.min(b).min(c), a.max(b).max(c))
+    }
+
+    /// Центр корабля (центр масс треугольника).
+    pub fn center(&self) -> Vec2 {
+        let [a, b, c] = self.vertices();
+        (a + b + c) / 3.0
+    }
+
+    /// Положение корабля (середины основания треугольника).
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Угол поворота корабля.
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// Положение носа корабля (верхней вершины треугольника).
+    pub fn nose(&self) -> Vec2 {
+        self.vertices()[0]
+    }
+
+    /// Середина основания корабля, откуда вылетает выхлоп, и направление выхлопа,
+    /// длина которого равна силе тяги. Если двигатель не работает, выхлопа нет.
+    pub fn exhaust(&self) -> Option<(Vec2, Vec2)> {
+        if self.thrust <= 0.0 {
+            return None;
+        }
+        let [_, left, right] = self.vertices();
+        Some(((left + right) / 2.0, -self.heading() * self.thrust))
+    }
+
+    /// Единичный вектор направления носа корабля.
+    pub fn heading(&self) -> Vec2 {
+        Vec2::new(self.rotation.sin(), -self.rotation.cos())
+    }
+
+    /// Перезарядка орудия за время `elapsed_time` и выстрел, если `fire` установлен.
+    /// Пуля вылетает из носа корабля по направлению его полёта.
+    pub fn fire(&mut self, elapsed_time: f64, fire: bool) -> Option<Bullet> {
+        let (nose, heading) = (self.nose(), self.heading());
+        self.weapons.update(elapsed_time, fire, nose, heading)
+    }
+
+    /// Скорость корабля на экране. В классическом режиме без движения
+    /// по вертикали корабль движется только по горизонтали.
+    pub fn velocity(&self) -> Vec2 {
+        if self.mode == FlightMode::Classic && !self.vertical_movement {
+            Vec2::new(self.velocity.x, 0.0)
+        } else {
+            self.velocity
+        }
+    }
+
+    /// Сглаженная скорость корабля по вертикали с учётом форсажа. С этой скоростью
+    /// астероиды и другие объекты движутся навстречу кораблю.
+    pub fn smoothed_vertical_speed(&self) -> f32 {
+        self.smoothed_vertical_speed
+    }
+
+    /// Скорость корабля по вертикали с учётом форсажа.
+    pub fn vertical_speed(&self) -> f32 {
+        if self.boosting {
+            self.vertical_speed + Self::BOOST_SPEED
+        } else {
+            self.vertical_speed
+        }
+    }
+
+    /// Доля оставшегося запаса форсажа от 0 до 1.
+    pub fn boost_fraction(&self) -> f32 {
+        self.boost_meter / Self::BOOST_METER_MAX
+    }
+
+    /// Число оставшихся жизней.
+    pub fn lives(&self) -> u32 {
+        self.lives
+    }
+
+    /// Установка числа жизней.
+    pub fn set_lives(&mut self, lives: u32) {
+        self.lives = lives;
+    }
+
+    /// Неуязвим ли корабль после недавнего столкновения.
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerability_timer > 0.0
+    }
+
+    /// Включение щита на `duration` секунд. Повторно подобранный щит продлевает действие.
+    pub fn activate_shield(&mut self, duration: f32) {
+        self.shield_timer = self.shield_timer.max(duration);
+    }
+
+    /// Оставшееся время действия щита.
+    pub fn shield_time(&self) -> f32 {
+        self.shield_timer
+    }
+
+    /// Возвращение корабля в начальное положение в окне размером `screen_size`
+    /// после столкновения в тренировке. Корабль останавливается и ненадолго
+    /// становится неуязвимым, но жизнь не теряет.
+    pub fn respawn(&mut self, screen_size: Vec2) {
+        self.position = Vec2::new(screen_size.x / 2.0, screen_size.y - Self::SHIP_OFFSET);
+        self.prev_position = self.position;
+        self.velocity = Vec2::ZERO;
+        self.rotation = 0.0;
+        self.invulnerability_timer = Self::INVULNERABILITY_DURATION;
+        self.trail.clear();
+    }
+
+    /// Обработка столкновения: корабль теряет жизнь и ненадолго становится неуязвимым.
+    /// Если действует щит, то удар принимает он и пропадает, а жизнь остаётся.
+    /// Возвращает, потеряна ли жизнь.
+    pub fn hit(&mut self) -> bool {
+        self.invulnerability_timer = Self::INVULNERABILITY_DURATION;
+        if self.shield_timer > 0.0 {
+            self.shield_timer = 0.0;
+            return false;
+        }
+        self.lives = self.lives.saturating_sub(1);
+        true
+    }
+}
+
+/// Пересекается ли треугольник `triangle` с кругом с центром `center` и радиусом `radius`.
+///
+/// Круг пересекает треугольник, если его центр лежит внутри треугольника
+/// или ближайшая к центру точка одной из сторон находится ближе радиуса.
+fn triangle_intersects_circle(triangle: [Vec2; 3], center: Vec2, radius: f32) -> bool {
+    if triangle_contains(triangle, center) {
+        return true;
+    }
+
+    let [a, b, c] = triangle;
+    [(a, b), (b, c), (c, a)].iter().any(|&(start, end)| {
+        (closest_point_on_segment(start, end, center) - center).length() < radius
+    })
+}
+
+/// Лежит ли точка `point` внутри треугольника `triangle` (или на его границе).
+fn triangle_contains(triangle: [Vec2; 3], point: Vec2) -> bool {
+    // Точка внутри, если она лежит по одну сторону от всех трёх сторон треугольника,
+    // независимо от порядка обхода вершин.
+    let [a, b, c] = triangle;
+    let side = |start: Vec2, end: Vec2| (end - start).perp_dot(point - start);
+    let sides = [side(a, b), side(b, c), side(c, a)];
+    sides.iter().all(|&side| side >= 0.0) || sides.iter().all(|&side| side <= 0.0)
+}
+
+/// Ближайшая к `point` точка отрезка от `start` до `end`.
+fn closest_point_on_segment(start: Vec2, end: Vec2, point: Vec2) -> Vec2 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared == 0.0 {
+        return start; // Вырожденный отрезок.
+    }
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    start + segment * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Треугольник, похожий на корабль: нос вверху, основание внизу.
+    fn triangle() -> [Vec2; 3] {
+        [
+            Vec2::new(0.0, -25.0),
+            Vec2::new(-12.5, 0.0),
+            Vec2::new(12.5, 0.0),
+        ]
+    }
+
+    #[test]
+    fn circle_inside_triangle_collides() {
+        assert!(triangle_intersects_circle(
+            triangle(),
+            Vec2::new(0.0, -5.0),
+            1.0
+        ));
+    }
+
+    #[test]
+    fn circle_outside_triangle_does_not_collide() {
+        assert!(!triangle_intersects_circle(
+            triangle(),
+            Vec2::new(0.0, 50.0),
+            10.0
+        ));
+        // Сбоку от носа: грубое приближение кругом здесь давало столкновение.
+        assert!(!triangle_intersects_circle(
+            triangle(),
+            Vec2::new(14.0, -14.0),
+            5.0
+        ));
+    }
+
+    #[test]
+    fn circle_grazing_edge() {
+        // Основание лежит на прямой y = 0: круг радиусом 10 с центром на 9.9 ниже задевает его,
+        // а с центром на 10.1 ниже - уже нет.
+        assert!(triangle_intersects_circle(
+            triangle(),
+            Vec2::new(0.0, 9.9),
+            10.0
+        ));
+        assert!(!triangle_intersects_circle(
+            triangle(),
+            Vec2::new(0.0, 10.1),
+            10.0
+        ));
+    }
+
+    #[test]
+    fn circle_near_vertex() {
+        // Ближе всего к центру круга оказывается вершина носа.
+        assert!(triangle_intersects_circle(
+            triangle(),
+            Vec2::new(0.0, -29.0),
+            5.0
+        ));
+        assert!(!triangle_intersects_circle(
+            triangle(),
+            Vec2::new(0.0, -31.0),
+            5.0
+        ));
+    }
+
+    #[test]
+    fn vertical_movement_stays_in_band() {
+        let screen_size = Vec2::new(800.0, 600.0);
+        let mut ship = Ship::new(
+            FlightMode::Classic,
+            false,
+            GameConfig::default(),
+            screen_size,
+        );
+        ship.allow_vertical_movement();
+        let (top, bottom) = Ship::vertical_band(screen_size.y);
+
+        // Долго летим вверх: корабль упирается в верхнюю границу полосы, а форсаж не включается.
+        let up = Input {
+            thrust: true,
+            ..Input::default()
+        };
+        for _ in 0..600 {
+            ship.update(up, 1.0 / 120.0, screen_size);
+        }
+        assert_eq!(ship.position.y, top);
+        assert_eq!(ship.position.x, screen_size.x / 2.0);
+        assert!(!ship.boosting);
+
+        // И вниз - до нижней.
+        let down = Input {
+            descend: true,
+            ..Input::default()
+        };
+        for _ in 0..600 {
+            ship.update(down, 1.0 / 120.0, screen_size);
+        }
+        assert_eq!(ship.position.y, bottom);
+    }
+
+    #[test]
+    fn vertical_speed_is_capped() {
+        let screen_size = Vec2::new(800.0, 600.0);
+        let config = GameConfig::default();
+        let mut ship = Ship::new(FlightMode::Classic, false, config, screen_size);
+        let start = ship.vertical_speed();
+
+        // В начале игры скорость растёт так же, как без предела.
+        for _ in 0..120 {
+            ship.update(Input::default(), 1.0 / 120.0, screen_size);
+        }
+        let expected = start + config.vertical_acceleration;
+        assert!((ship.vertical_speed() - expected).abs() < 0.01);
+
+        // А через десять минут упирается в предел.
+        for _ in 0..120 * 600 {
+            ship.update(Input::default(), 1.0 / 120.0, screen_size);
+        }
+        assert_eq!(ship.vertical_speed(), Ship::MAX_VERTICAL_SPEED);
+    }
+
+    #[test]
+    fn collision_radius_encloses_triangle() {
+        let screen_size = Vec2::new(800.0, 600.0);
+        let ship = Ship::new(
+            FlightMode::Classic,
+            false,
+            GameConfig::default(),
+            screen_size,
+        );
+        // Пока круг описывает весь треугольник, столкновения определяет только треугольник.
+        let center = ship.center();
+        for vertex in ship.vertices() {
+            assert!(vertex.distance(center) <= ship.collision_radius());
+        }
+        // Круг, задевающий нос, сталкивается с кораблём.
+        let [top, _, _] = ship.vertices();
+        assert!(ship.is_collapse(top - Vec2::new(0.0, 4.0), 5.0));
+    }
+
+    #[test]
+    fn horizontal_speed_decays_to_rest_without_changing_sign() {
+        let screen_size = Vec2::new(800.0, 600.0);
+        // Последний шаг длиннее времени, за которое торможение гасит всю скорость.
+        for elapsed_time in [1.0 / 240.0, 1.0 / 120.0, 1.0 / 60.0, 0.1, 0.5] {
+            for start in [300.0, -300.0] {
+                // Корабль перелетает края, чтобы его не остановила стенка.
+                let mut ship = Ship::new(
+                    FlightMode::Classic,
+                    true,
+                    GameConfig::default(),
+                    screen_size,
+                );
+                ship.velocity.x = start;
+                let mut time = 0.0;
+                while ship.velocity.x != 0.0 {
+                    let previous = ship.velocity.x;
+                    ship.update(Input::default(), elapsed_time, screen_size);
+                    time += elapsed_time;
+                    assert!(
+                        ship.velocity.x.abs() < previous.abs(),
+                        "speed grew from {} to {} at step {}",
+                        previous,
+                        ship.velocity.x,
+                        elapsed_time
+                    );
+                    assert!(
+                        ship.velocity.x * start >= 0.0,
+                        "speed changed sign at step {}",
+                        elapsed_time
+                    );
+                    assert!(time < 5.0, "ship still moves at step {}", elapsed_time);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn smoothed_vertical_speed_follows_without_jumps() {
+        let screen_size = Vec2::new(800.0, 600.0);
+        let config = GameConfig {
+            vertical_acceleration: 0.0,
+            ..GameConfig::default()
+        };
+        let mut ship = Ship::new(FlightMode::Classic, false, config, screen_size);
+        // Скорость резко выросла: сглаженная за один шаг меняется ненамного,
+        let start = ship.smoothed_vertical_speed();
+        ship.vertical_speed = 900.0;
+        ship.update(Input::default(), 1.0 / 120.0, screen_size);
+        assert!(ship.smoothed_vertical_speed() - start < 100.0);
+        // но через секунду почти догоняет настоящую.
+        for _ in 0..120 {
+            ship.update(Input::default(), 1.0 / 120.0, screen_size);
+        }
+        assert!((ship.smoothed_vertical_speed() - 900.0).abs() < 1.0);
+    }
+}
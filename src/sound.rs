@@ -0,0 +1,86 @@
+//! Звуковые эффекты.
+
+use macroquad::audio::{load_sound, play_sound, stop_sound, PlaySoundParams, Sound};
+use macroquad::prelude::*;
+
+/// Звуковые эффекты игры.
+///
+/// Звук, файл которого не удалось загрузить, заменяется тишиной.
+#[derive(Clone, Copy, Default)]
+pub struct Sounds {
+    /// Взрыв астероида.
+    explosion: Option<Sound>,
+    /// Работа двигателя (воспроизводится по кругу).
+    thrust: Option<Sound>,
+    /// Выстрел.
+    shoot: Option<Sound>,
+    /// Громкость от 0 до 1.
+    volume: f32,
+}
+
+impl Sounds {
+    /// Загрузка звуков из каталога `assets`.
+    pub async fn load() -> Self {
+        Self {
+            explosion: load_optional("assets/explosion.wav").await,
+            thrust: load_optional("assets/thrust.wav").await,
+            shoot: load_optional("assets/shoot.wav").await,
+            volume: 1.0,
+        }
+    }
+
+    /// Копия звуков с громкостью `volume`.
+    pub fn with_volume(self, volume: f32) -> Self {
+        Self { volume, ..self }
+    }
+
+    /// Звук взрыва.
+    pub fn play_explosion(&self) {
+        self.play_once(self.explosion);
+    }
+
+    /// Звук выстрела.
+    pub fn play_shoot(&self) {
+        self.play_once(self.shoot);
+    }
+
+    /// Запуск звука двигателя по кругу.
+    pub fn start_thrust(&self) {
+        if let Some(sound) = self.thrust {
+            let params = PlaySoundParams {
+                looped: true,
+                volume: self.volume,
+            };
+            play_sound(sound, params);
+        }
+    }
+
+    /// Остановка звука двигателя.
+    pub fn stop_thrust(&self) {
+        if let Some(sound) = self.thrust {
+            stop_sound(sound);
+        }
+    }
+
+    /// Однократное воспроизведение звука, если он загружен.
+    fn play_once(&self, sound: Option<Sound>) {
+        if let Some(sound) = sound {
+            let params = PlaySoundParams {
+                looped: false,
+                volume: self.volume,
+            };
+            play_sound(sound, params);
+        }
+    }
+}
+
+/// Загрузка звука. Если загрузить не удалось, то звука нет.
+async fn load_optional(path: &str) -> Option<Sound> {
+    match load_sound(path).await {
+        Ok(sound) => Some(sound),
+        Err(err) => {
+            warn!("Failed to load sound {}: {}", path, err);
+            None
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Пространственная сетка для быстрого поиска близких объектов.
+
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+/// Равномерная сетка, разбивающая плоскость на квадратные ячейки.
+/// Каждый объект заносится в ячейку, в которой находится его центр,
+/// поэтому при поиске достаточно просмотреть только ячейки нужной области,
+/// расширенной на радиус самого крупного объекта.
+pub struct SpatialGrid {
+    /// Размер стороны ячейки в пикселях.
+    cell_size: f32,
+    /// Индексы объектов в каждой ячейке.
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    /// Радиус самого крупного объекта в сетке.
+    max_radius: f32,
+}
+
+impl SpatialGrid {
+    /// Создание пустой сетки с ячейками размера `cell_size`.
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            max_radius: 0.0,
+        }
+    }
+
+    /// Удаление всех объектов из сетки. Память ячеек сохраняется для следующего заполнения.
+    pub fn clear(&mut self) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+        self.max_radius = 0.0;
+    }
+
+    /// Добавление объекта с индексом `index` - круга с центром `position` и радиусом `radius`.
+    pub fn insert(&mut self, index: usize, position: Vec2, radius: f32) {
+        let cell = self.cell(position);
+        self.cells.entry(cell).or_default().push(index);
+        self.max_radius = self.max_radius.max(radius);
+    }
+
+    /// Индексы объектов, которые могут пересекаться с прямоугольником от `min` до `max`.
+    /// Индексы не повторяются, но идут в произвольном порядке. Поиск не выделяет память.
+    pub fn query_region(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = usize> + '_ {
+        let offset = Vec2::new(self.max_radius, self.max_radius);
+        let (min, max) = (self.cell(min - offset), self.cell(max + offset));
+        (min.0..=max.0)
+            .flat_map(move |x| (min.1..=max.1).map(move |y| (x, y)))
+            .filter_map(move |cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+
+    /// Ячейка, в которой находится точка `point`.
+    fn cell(&self, point: Vec2) -> (i32, i32) {
+        (
+            (point.x / self.cell_size).floor() as i32,
+            (point.y / self.cell_size).floor() as i32,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Случайные круги, разбросанные по области 1000 на 1000 пикселей.
+    fn circles(count: usize) -> Vec<(Vec2, f32)> {
+        let mut rng = crate::rng::Rng::new(42);
+        (0..count)
+            .map(|_| {
+                let position = Vec2::new(rng.gen_range(0.0, 1000.0), rng.gen_range(0.0, 1000.0));
+                (position, rng.gen_range(25.0, 100.0))
+            })
+            .collect()
+    }
+
+    /// Пересекается ли круг с прямоугольником.
+    fn intersects(position: Vec2, radius: f32, min: Vec2, max: Vec2) -> bool {
+        let nearest = position.max(min).min(max);
+        (position - nearest).length() < radius
+    }
+
+    #[test]
+    fn query_finds_every_intersecting_circle() {
+        let circles = circles(300);
+        let mut grid = SpatialGrid::new(128.0);
+        for (index, &(position, radius)) in circles.iter().enumerate() {
+            grid.insert(index, position, radius);
+        }
+
+        let (min, max) = (Vec2::new(450.0, 900.0), Vec2::new(500.0, 950.0));
+        let found: Vec<usize> = grid.query_region(min, max).collect();
+        for (index, &(position, radius)) in circles.iter().enumerate() {
+            if intersects(position, radius, min, max) {
+                assert!(found.contains(&index), "circle {} was not found", index);
+            }
+        }
+    }
+
+    #[test]
+    fn query_skips_distant_cells() {
+        let mut grid = SpatialGrid::new(10.0);
+        grid.insert(0, Vec2::new(0.0, 0.0), 25.0);
+        grid.insert(1, Vec2::new(5.0, 5.0), 1.0);
+        let mut found: Vec<usize> = grid
+            .query_region(Vec2::new(-30.0, -30.0), Vec2::new(30.0, 30.0))
+            .collect();
+        found.sort_unstable();
+        assert_eq!(found, [0, 1]);
+        assert!(grid
+            .query_region(Vec2::new(100.0, 100.0), Vec2::new(110.0, 110.0))
+            .next()
+            .is_none());
+    }
+}
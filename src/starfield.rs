@@ -0,0 +1,71 @@
+//! Звёздный фон.
+
+use macroquad::prelude::*;
+use macroquad::rand::RandomRange;
+use serde::{Deserialize, Serialize};
+
+/// Звёздный фон, создающий ощущение глубины.
+#[derive(Serialize, Deserialize)]
+pub struct Starfield {
+    /// Положения звёзд и их удалённость от 0 (далеко) до 1 (близко).
+    stars: Vec<(Vec2, f32)>,
+}
+
+impl Starfield {
+    // Параметры звёзд.
+    const MIN_DEPTH: f32 = 0.1;
+    const MAX_DEPTH: f32 = 0.5;
+    const MAX_RADIUS: f32 = 2.0;
+
+    /// Создание фона из `count` звёзд, случайно разбросанных по экрану размером `screen_size`.
+    pub fn new(count: usize, screen_size: Vec2) -> Self {
+        let mut starfield = Self { stars: Vec::new() };
+        starfield.set_count(count, screen_size);
+        starfield
+    }
+
+    /// Изменение числа звёзд до `count`. Лишние звёзды исчезают,
+    /// а недостающие случайно разбрасываются по экрану размером `screen_size`.
+    pub fn set_count(&mut self, count: usize, screen_size: Vec2) {
+        self.stars.truncate(count);
+        let missing = count.saturating_sub(self.stars.len());
+        self.stars.extend((0..missing).map(|_| {
+            let position = Vec2::new(
+                f32::gen_range(0.0, screen_size.x),
+                f32::gen_range(0.0, screen_size.y),
+            );
+            (position, f32::gen_range(Self::MIN_DEPTH, Self::MAX_DEPTH))
+        }));
+    }
+
+    /// Прокрутка звёзд вниз по экрану размером `screen_size`.
+    /// Более близкие звёзды движутся быстрее.
+    pub fn update(&mut self, elapsed_time: f64, vertical_speed: f32, screen_size: Vec2) {
+        let elapsed_time = elapsed_time as f32;
+        for (position, depth) in &mut self.stars {
+            position.y += vertical_speed * *depth * elapsed_time;
+
+            // Звезда, ушедшая за нижний край, появляется сверху в случайном месте.
+            if position.y > screen_size.y {
+                position.y -= screen_size.y;
+                position.x = f32::gen_range(0.0, screen_size.x);
+            }
+        }
+    }
+
+    /// Растягивание звёздного фона на окно изменившегося размера.
+    pub fn rescale(&mut self, scale: Vec2) {
+        for (position, _) in &mut self.stars {
+            *position *= scale;
+        }
+    }
+
+    /// Отображение звёзд. Более близкие звёзды крупнее и ярче.
+    pub fn draw(&self) {
+        for (position, depth) in &self.stars {
+            let brightness = depth / Self::MAX_DEPTH;
+            let color = Color::new(1.0, 1.0, 1.0, brightness);
+            draw_circle(position.x, position.y, Self::MAX_RADIUS * brightness, color);
+        }
+    }
+}
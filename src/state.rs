@@ -0,0 +1,1366 @@
+//! Состояние приложения: меню, запуск игры и учёт рекорда.
+
+use crate::config::GameConfig;
+use crate::daily::{self, DailyBest};
+use crate::difficulty::Difficulty;
+use crate::game::{Game, RunStats};
+use crate::ghost::Ghost;
+use crate::input::{Arrows, Gamepad, KeyBindings};
+use crate::lang::TextKey;
+use crate::leaderboard::{Entry, Leaderboard};
+use crate::replay::Replay;
+use crate::settings::Settings;
+use crate::ship::FlightMode;
+use crate::sound::Sounds;
+use crate::stats::TotalStats;
+use crate::storage;
+use crate::theme::Theme;
+use crate::time_attack::{self, TimeAttackBest};
+use crate::ui::draw_centered_text;
+use macroquad::prelude::*;
+
+/// Пункты главного меню.
+#[derive(Clone, Copy)]
+enum MenuItem {
+    /// Новая игра.
+    Play,
+    /// Тренировка.
+    Practice,
+    /// Ежедневное испытание.
+    Daily,
+    /// Режим на время.
+    TimeAttack,
+    /// Просмотр записи последней игры.
+    Replay,
+    /// Экран настроек.
+    Options,
+    /// Таблица лучших результатов.
+    Leaderboard,
+    /// Общая статистика.
+    Stats,
+    /// Выход из приложения. В браузере выходить некуда, поэтому там этого пункта нет.
+    #[cfg(not(target_arch = "wasm32"))]
+    Quit,
+}
+
+impl MenuItem {
+    /// Все пункты в порядке их отображения.
+    #[cfg(not(target_arch = "wasm32"))]
+    const ALL: [MenuItem; 9] = [
+        MenuItem::Play,
+        MenuItem::Practice,
+        MenuItem::Daily,
+        MenuItem::TimeAttack,
+        MenuItem::Replay,
+        MenuItem::Options,
+        MenuItem::Leaderboard,
+        MenuItem::Stats,
+        MenuItem::Quit,
+    ];
+
+    /// Все пункты в порядке их отображения.
+    #[cfg(target_arch = "wasm32")]
+    const ALL: [MenuItem; 8] = [
+        MenuItem::Play,
+        MenuItem::Practice,
+        MenuItem::Daily,
+        MenuItem::TimeAttack,
+        MenuItem::Replay,
+        MenuItem::Options,
+        MenuItem::Leaderboard,
+        MenuItem::Stats,
+    ];
+}
+
+/// Пункты меню паузы.
+#[derive(Clone, Copy)]
+enum PauseItem {
+    /// Продолжение игры.
+    Resume,
+    /// Новая игра того же вида.
+    Restart,
+    /// Завершение игры с подведением итогов.
+    End,
+}
+
+impl PauseItem {
+    /// Все пункты в порядке их отображения.
+    const ALL: [PauseItem; 3] = [PauseItem::Resume, PauseItem::Restart, PauseItem::End];
+
+    /// Название пункта.
+    fn name(self) -> TextKey {
+        match self {
+            PauseItem::Resume => TextKey::PauseResume,
+            PauseItem::Restart => TextKey::PauseRestart,
+            PauseItem::End => TextKey::PauseEnd,
+        }
+    }
+}
+
+/// Состояние главного меню. Хранится и при уходе с меню, чтобы при возвращении
+/// был выбран тот же пункт.
+#[derive(Default)]
+struct MenuState {
+    /// Индекс выбранного пункта в [`MenuItem::ALL`].
+    selected: usize,
+}
+
+/// Экран, на котором находится приложение.
+enum Screen {
+    /// Главное меню.
+    Menu,
+    /// Выбор уровня сложности перед началом игры.
+    ChooseDifficulty {
+        /// Индекс выбранного уровня в [`Difficulty::ALL`].
+        selected: usize,
+    },
+    /// Идёт игра (или воспроизводится её запись).
+    Playing(Box<Game>),
+    /// Игра поставлена на паузу, поверх неё показано меню паузы.
+    Paused {
+        /// Игра.
+        game: Box<Game>,
+        /// Индекс выбранного пункта в [`PauseItem::ALL`].
+        selected: usize,
+    },
+    /// Экран настроек.
+    Options {
+        /// Индекс выбранного пункта в [`OptionItem::ALL`].
+        selected: usize,
+    },
+    /// Таблица лучших результатов.
+    Leaderboard,
+    /// Общая статистика по всем играм.
+    Stats,
+    /// Ввод инициалов для результата, попавшего в таблицу.
+    NewEntry {
+        /// Статистика игры.
+        stats: RunStats,
+        /// Установлен ли в этой игре новый рекорд.
+        new_record: bool,
+        /// Вводимые инициалы (заглавные латинские буквы).
+        initials: [u8; 3],
+        /// Индекс изменяемой буквы.
+        cursor: usize,
+    },
+    /// Итоги завершённой игры.
+    GameOver {
+        /// Статистика игры.
+        stats: RunStats,
+        /// Установлен ли в этой игре новый рекорд.
+        new_record: bool,
+    },
+}
+
+/// Пункты экрана настроек.
+#[derive(Clone, Copy)]
+enum OptionItem {
+    /// Общая громкость.
+    Volume,
+    /// Уровень сложности.
+    Difficulty,
+    /// Язык интерфейса.
+    Language,
+    /// Поведение корабля у краёв экрана.
+    Edges,
+    /// Управление кораблём с клавиатуры или мышью.
+    Steering,
+    /// Цветовая тема.
+    Theme,
+    /// Появление астероидов непрерывным потоком или волнами.
+    Spawning,
+    /// Движение корабля по вертикали в классическом режиме.
+    Vertical,
+    /// Мёртвая зона стика геймпада.
+    DeadZone,
+    /// Кривая отклика стика геймпада.
+    Curve,
+    /// Различение объектов по форме.
+    Shapes,
+    /// Ограничение частоты кадров.
+    FrameLimit,
+    /// Зерно генератора случайных чисел.
+    Seed,
+}
+
+impl OptionItem {
+    /// Все пункты в порядке их отображения.
+    const ALL: [OptionItem; 13] = [
+        OptionItem::Volume,
+        OptionItem::Difficulty,
+        OptionItem::Language,
+        OptionItem::Edges,
+        OptionItem::Steering,
+        OptionItem::Theme,
+        OptionItem::Spawning,
+        OptionItem::Vertical,
+        OptionItem::DeadZone,
+        OptionItem::Curve,
+        OptionItem::Shapes,
+        OptionItem::FrameLimit,
+        OptionItem::Seed,
+    ];
+}
+
+/// Состояние приложения.
+pub struct State {
+    /// Рекордное время на каждом уровне сложности, в порядке [`Difficulty::ALL`].
+    best_times: BestTimes,
+    /// Таблица лучших результатов.
+    leaderboard: Leaderboard,
+    /// Общая статистика по всем играм.
+    stats: TotalStats,
+    /// Лучшее время ежедневного испытания.
+    daily: DailyBest,
+    /// Лучший результат режима на время.
+    time_attack: TimeAttackBest,
+    /// Призрак лучшей игры с заданным зерном (ключом `--seed` или в ежедневном испытании).
+    ghost: Option<Ghost>,
+    /// Лучший счёт за время работы приложения.
+    best_score: u64,
+    /// Текущий экран.
+    screen: Screen,
+    /// Выбранный пункт главного меню.
+    menu: MenuState,
+    /// Пункт меню, которым была начата последняя игра. Повторяется при перезапуске с паузы.
+    started: MenuItem,
+    /// Выбран ли выход из приложения.
+    quit: bool,
+    /// Режим управления кораблём, выбранный в меню.
+    flight_mode: FlightMode,
+    /// Назначение клавиш управления.
+    key_bindings: KeyBindings,
+    /// Ввод с геймпада.
+    gamepad: Gamepad,
+    /// Зерно генератора случайных чисел, заданное ключом `--seed` или в настройках.
+    /// Если задано, то каждая игра повторяет одну и ту же последовательность астероидов.
+    seed: Option<u64>,
+    /// Звуковые эффекты.
+    sounds: Sounds,
+    /// Громкость, уровень сложности и язык.
+    settings: Settings,
+    /// Настройки игровой механики из файла `config.toml`.
+    config: GameConfig,
+    /// Демонстрационная игра с автопилотом, идущая на фоне меню.
+    demo: Game,
+}
+
+impl State {
+    /// Шаг изменения громкости в меню.
+    const VOLUME_STEP: f32 = 0.1;
+    /// Шаг изменения мёртвой зоны стика в настройках.
+    const DEAD_ZONE_STEP: f32 = 0.05;
+
+    /// Логика создания состояния приложения. Если в командной строке указан файл записи
+    /// (`--replay replay.bin`), то сразу начинается её воспроизведение.
+    pub fn new(sounds: Sounds) -> Self {
+        let settings = Settings::load();
+        let config = GameConfig::load();
+        let mut state = Self {
+            best_times: load_best_times(),
+            leaderboard: Leaderboard::load(),
+            stats: TotalStats::load(),
+            daily: DailyBest::load(),
+            time_attack: TimeAttackBest::load(),
+            ghost: Ghost::load(),
+            best_score: 0,
+            screen: Screen::Menu, // Изначально находимся в меню.
+            menu: MenuState::default(),
+            started: MenuItem::Play,
+            quit: false,
+            flight_mode: FlightMode::Classic,
+            key_bindings: KeyBindings::load(),
+            gamepad: Gamepad::new(),
+            seed: seed_from_args(),
+            sounds,
+            settings,
+            config,
+            demo: Game::demo(settings.difficulty, config),
+        };
+        if let Some(replay) = replay_from_args() {
+            state.watch_replay(replay);
+        }
+        state
+    }
+
+    /// Наибольшая частота кадров или 0, если она не ограничена.
+    pub fn frame_limit(&self) -> u32 {
+        self.settings.frame_limit
+    }
+
+    /// Выбран ли в меню выход из приложения.
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Логика обновления приложения.
+    pub fn update(&mut self) {
+        // Опрашиваем геймпад один раз за кадр.
+        self.gamepad.poll();
+
+        // Нажатия паузы и выхода проверяем заранее: ниже экран будет заимствован.
+        let pause_pressed = self.is_pause_pressed();
+        let quit_pressed = is_key_pressed(self.key_bindings.quit);
+        let arrows = Arrows::read(&self.gamepad);
+        match &mut self.screen {
+            Screen::Menu => self.update_menu(),
+            Screen::Playing(game) => {
+                // Клавиша выхода тоже ставит игру на паузу, кроме предложения продолжить
+                // игру после гибели: там она означает отказ, и его обрабатывает сама игра.
+                let quit_pressed = quit_pressed && !game.is_continue_offered();
+                if pause_pressed || quit_pressed {
+                    // Если нажата клавиша паузы (по умолчанию P) или выхода (Escape)
+                    // или кнопка паузы на геймпаде - ставим игру на паузу.
+                    self.toggle_pause();
+                } else if let Some(stats) = game.update(
+                    &self.gamepad,
+                    self.settings.mouse_steering,
+                    self.settings.axis_response(),
+                ) {
+                    // Если игра завершена - то получим её итоги.
+                    self.finish_game(stats);
+                } else if game.take_focus_lost() {
+                    // Если окно теряло фокус - ставим игру на паузу, чтобы игрок
+                    // не вернулся к уже разбитому кораблю.
+                    self.toggle_pause();
+                }
+            }
+            Screen::Paused { selected, .. } => {
+                let selected = *selected;
+                if pause_pressed || quit_pressed {
+                    self.toggle_pause();
+                } else {
+                    self.update_pause_menu(selected);
+                }
+            }
+            Screen::ChooseDifficulty { selected } => {
+                let selected = *selected;
+                if quit_pressed {
+                    self.screen = Screen::Menu;
+                } else {
+                    self.update_choose_difficulty(selected, arrows);
+                }
+            }
+            Screen::Options { selected } => {
+                let selected = *selected;
+                self.update_options(selected);
+            }
+            Screen::Leaderboard => {
+                // По нажатию L, клавиши выхода или старта возвращаемся в меню.
+                if is_key_pressed(KeyCode::L)
+                    || is_key_pressed(self.key_bindings.quit)
+                    || self.is_start_pressed()
+                {
+                    self.screen = Screen::Menu;
+                }
+            }
+            Screen::Stats => {
+                // По нажатию S, клавиши выхода или старта возвращаемся в меню.
+                if is_key_pressed(KeyCode::S)
+                    || is_key_pressed(self.key_bindings.quit)
+                    || self.is_start_pressed()
+                {
+                    self.screen = Screen::Menu;
+                }
+            }
+            Screen::NewEntry {
+                initials, cursor, ..
+            } => {
+                // Стрелками влево и вправо выбираем букву, вверх и вниз - меняем её.
+                if arrows.left {
+                    *cursor = cursor.saturating_sub(1);
+                }
+                if arrows.right {
+                    *cursor = (*cursor + 1).min(initials.len() - 1);
+                }
+                let letter = &mut initials[*cursor];
+                if arrows.up {
+                    *letter = if *letter == b'Z' { b'A' } else { *letter + 1 };
+                }
+                if arrows.down {
+                    *letter = if *letter == b'A' { b'Z' } else { *letter - 1 };
+                }
+
+                if self.is_start_pressed() {
+                    self.add_entry();
+                }
+            }
+            Screen::GameOver { .. } => {
+                // По нажатию клавиши старта возвращаемся в меню,
+                if self.is_start_pressed() {
+                    self.screen = Screen::Menu;
+                // а по нажатию R смотрим запись только что сыгранной игры.
+                } else if is_key_pressed(KeyCode::R) {
+                    if let Some(replay) = Replay::load() {
+                        self.watch_replay(replay);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Логика обновления меню.
+    fn update_menu(&mut self) {
+        // Демонстрация начинается заново, когда автопилот теряет все жизни.
+        let response = self.settings.axis_response();
+        if self.demo.update(&self.gamepad, false, response).is_some() {
+            self.demo = Game::demo(self.settings.difficulty, self.config);
+        }
+
+        // Если нажата F - переключаем режим управления кораблём.
+        if is_key_pressed(KeyCode::F) {
+            self.flight_mode = self.flight_mode.next();
+        }
+
+        // Клавишами - и = меняем громкость.
+        if is_key_pressed(KeyCode::Minus) {
+            self.change_volume(-Self::VOLUME_STEP);
+            self.settings.save();
+        }
+        if is_key_pressed(KeyCode::Equal) {
+            self.change_volume(Self::VOLUME_STEP);
+            self.settings.save();
+        }
+
+        // Клавишами вверх и вниз выбираем пункт.
+        let arrows = Arrows::read(&self.gamepad);
+        let selected = &mut self.menu.selected;
+        if arrows.up {
+            *selected = selected.saturating_sub(1);
+        }
+        if arrows.down {
+            *selected = (*selected + 1).min(MenuItem::ALL.len() - 1);
+        }
+
+        // Клавиша старта (по умолчанию Enter), кнопка старта на геймпаде или касание
+        // экрана выбирают отмеченный пункт, а у большинства пунктов есть и своя клавиша.
+        let item = if self.is_start_pressed() {
+            Some(MenuItem::ALL[self.menu.selected])
+        } else if is_key_pressed(KeyCode::T) {
+            Some(MenuItem::Practice)
+        } else if is_key_pressed(KeyCode::C) {
+            Some(MenuItem::Daily)
+        } else if is_key_pressed(KeyCode::G) {
+            Some(MenuItem::TimeAttack)
+        } else if is_key_pressed(KeyCode::R) {
+            Some(MenuItem::Replay)
+        } else if is_key_pressed(KeyCode::O) {
+            Some(MenuItem::Options)
+        } else if is_key_pressed(KeyCode::L) {
+            Some(MenuItem::Leaderboard)
+        } else if is_key_pressed(KeyCode::S) {
+            Some(MenuItem::Stats)
+        } else {
+            None
+        };
+        if let Some(item) = item {
+            self.select_menu_item(item);
+        }
+    }
+
+    /// Переход с меню по выбранному пункту `item`.
+    fn select_menu_item(&mut self, item: MenuItem) {
+        if matches!(
+            item,
+            MenuItem::Play
+                | MenuItem::Practice
+                | MenuItem::Daily
+                | MenuItem::TimeAttack
+                | MenuItem::Replay
+        ) {
+            self.started = item;
+        }
+        match item {
+            // Перед обычной игрой выбираем уровень сложности, начиная с выбранного в настройках.
+            MenuItem::Play => {
+                let selected = self.settings.difficulty.index();
+                self.screen = Screen::ChooseDifficulty { selected };
+            }
+            MenuItem::Practice => {
+                let game = self.new_game().with_practice();
+                self.screen = Screen::Playing(Box::new(game));
+            }
+            MenuItem::Daily => self.screen = Screen::Playing(Box::new(self.daily_game())),
+            MenuItem::TimeAttack => {
+                self.screen = Screen::Playing(Box::new(self.time_attack_game()));
+            }
+            // Если записи последней игры нет, остаёмся в меню.
+            MenuItem::Replay => {
+                if let Some(replay) = Replay::load() {
+                    self.watch_replay(replay);
+                }
+            }
+            MenuItem::Options => self.screen = Screen::Options { selected: 0 },
+            MenuItem::Leaderboard => self.screen = Screen::Leaderboard,
+            MenuItem::Stats => self.screen = Screen::Stats,
+            #[cfg(not(target_arch = "wasm32"))]
+            MenuItem::Quit => self.quit = true,
+        }
+    }
+
+    /// Создание новой игры с выбранными в меню и настройках параметрами.
+    fn new_game(&self) -> Game {
+        let sounds = self.sounds.with_volume(self.settings.volume);
+        let (mode, wrap) = (self.flight_mode, self.settings.wrap_edges);
+        let (difficulty, config, keys) = (self.settings.difficulty, self.config, self.key_bindings);
+        let game = match self.seed {
+            Some(seed) => Game::with_seed(mode, wrap, difficulty, config, keys, sounds, seed),
+            None => Game::new(mode, wrap, difficulty, config, keys, sounds),
+        };
+        let game = if self.settings.waves {
+            game.with_waves()
+        } else {
+            game
+        };
+        let game = if self.settings.vertical_movement {
+            game.with_vertical_movement()
+        } else {
+            game
+        };
+        self.with_ghost(game)
+    }
+
+    /// Добавление к игре `game` призрака лучшей игры, если он записан с тем же зерном.
+    fn with_ghost(&self, game: Game) -> Game {
+        match &self.ghost {
+            Some(ghost) => game.with_ghost(ghost),
+            None => game,
+        }
+    }
+
+    /// Создание сегодняшнего ежедневного испытания. Чтобы игра у всех была одинаковой,
+    /// сложность всегда обычная, а астероиды идут потоком. Режим управления
+    /// и поведение у краёв экрана остаются выбранными игроком.
+    fn daily_game(&self) -> Game {
+        let date = daily::today();
+        let sounds = self.sounds.with_volume(self.settings.volume);
+        let (mode, wrap) = (self.flight_mode, self.settings.wrap_edges);
+        let game = Game::with_seed(
+            mode,
+            wrap,
+            Difficulty::Normal,
+            self.config,
+            self.key_bindings,
+            sounds,
+            daily::seed(date),
+        );
+        let game = if self.settings.vertical_movement {
+            game.with_vertical_movement()
+        } else {
+            game
+        };
+        self.with_ghost(game.with_daily(date))
+    }
+
+    /// Создание игры в режиме на время. Сложность всегда наибольшая, а остальные
+    /// параметры выбраны игроком.
+    fn time_attack_game(&self) -> Game {
+        let sounds = self.sounds.with_volume(self.settings.volume);
+        let (mode, wrap) = (self.flight_mode, self.settings.wrap_edges);
+        let (difficulty, config, keys) = (time_attack::DIFFICULTY, self.config, self.key_bindings);
+        let game = match self.seed {
+            Some(seed) => Game::with_seed(mode, wrap, difficulty, config, keys, sounds, seed),
+            None => Game::new(mode, wrap, difficulty, config, keys, sounds),
+        };
+        let game = if self.settings.vertical_movement {
+            game.with_vertical_movement()
+        } else {
+            game
+        };
+        self.with_ghost(game.with_time_attack(time_attack::GOAL))
+    }
+
+    /// Логика обновления экрана настроек с выбранным пунктом `selected`.
+    fn update_options(&mut self, mut selected: usize) {
+        // Клавишами вверх и вниз выбираем пункт.
+        let arrows = Arrows::read(&self.gamepad);
+        if arrows.up {
+            selected = selected.saturating_sub(1);
+        }
+        if arrows.down {
+            selected = (selected + 1).min(OptionItem::ALL.len() - 1);
+        }
+
+        // Клавишами влево и вправо меняем значение выбранного пункта.
+        let (left, right) = (arrows.left, arrows.right);
+        match OptionItem::ALL[selected] {
+            OptionItem::Volume if left => self.change_volume(-Self::VOLUME_STEP),
+            OptionItem::Volume if right => self.change_volume(Self::VOLUME_STEP),
+            OptionItem::Difficulty if left => {
+                self.settings.difficulty = self.settings.difficulty.previous();
+            }
+            OptionItem::Difficulty if right => {
+                self.settings.difficulty = self.settings.difficulty.next();
+            }
+            // Языков всего два, поэтому обе стрелки просто переключают язык.
+            OptionItem::Language if left || right => {
+                self.settings.lang = self.settings.lang.next();
+            }
+            OptionItem::Edges if left || right => {
+                self.settings.wrap_edges = !self.settings.wrap_edges;
+            }
+            OptionItem::Steering if left || right => {
+                self.settings.mouse_steering = !self.settings.mouse_steering;
+            }
+            OptionItem::Theme if left => self.settings.theme = self.settings.theme.previous(),
+            OptionItem::Theme if right => self.settings.theme = self.settings.theme.next(),
+            OptionItem::Spawning if left || right => {
+                self.settings.waves = !self.settings.waves;
+            }
+            OptionItem::Vertical if left || right => {
+                self.settings.vertical_movement = !self.settings.vertical_movement;
+            }
+            OptionItem::DeadZone if left => self.change_dead_zone(-Self::DEAD_ZONE_STEP),
+            OptionItem::DeadZone if right => self.change_dead_zone(Self::DEAD_ZONE_STEP),
+            // Кривых всего две, поэтому обе стрелки просто переключают кривую.
+            OptionItem::Curve if left || right => {
+                self.settings.curve = self.settings.curve.next();
+            }
+            OptionItem::Shapes if left || right => {
+                self.settings.shapes = !self.settings.shapes;
+            }
+            OptionItem::FrameLimit if left => self.change_frame_limit(-1),
+            OptionItem::FrameLimit if right => self.change_frame_limit(1),
+            // Левее наименьшего зерна - случайное зерно в каждой игре.
+            OptionItem::Seed if left => self.seed = self.seed.and_then(|seed| seed.checked_sub(1)),
+            OptionItem::Seed if right => {
+                self.seed = Some(self.seed.map_or(0, |seed| seed.saturating_add(1)));
+            }
+            OptionItem::Seed => self.type_seed(),
+            _ => {}
+        }
+
+        // По нажатию O или клавиши выхода сохраняем настройки и возвращаемся в меню.
+        self.screen = if is_key_pressed(KeyCode::O) || is_key_pressed(self.key_bindings.quit) {
+            self.settings.save();
+            Screen::Menu
+        } else {
+            Screen::Options { selected }
+        };
+    }
+
+    /// Изменение громкости на `delta` в пределах от 0 до 1.
+    fn change_volume(&mut self, delta: f32) {
+        self.settings.volume = (self.settings.volume + delta).clamp(0.0, 1.0);
+    }
+
+    /// Выбор соседнего варианта ограничения частоты кадров: следующего при `step`,
+    /// равном 1, и предыдущего при -1.
+    fn change_frame_limit(&mut self, step: isize) {
+        let limits = Settings::FRAME_LIMITS;
+        // Значение из файла настроек может не совпадать ни с одним вариантом.
+        let index = limits
+            .iter()
+            .position(|&limit| limit == self.settings.frame_limit)
+            .unwrap_or(0);
+        let index = index.saturating_add_signed(step).min(limits.len() - 1);
+        self.settings.frame_limit = limits[index];
+    }
+
+    /// Ввод зерна с клавиатуры: цифра дописывается к зерну справа, а Backspace
+    /// стирает последнюю цифру. Если стереть все цифры, зерно снова случайное.
+    fn type_seed(&mut self) {
+        if is_key_pressed(KeyCode::Backspace) {
+            self.seed = self.seed.filter(|&seed| seed >= 10).map(|seed| seed / 10);
+        } else if let Some(digit) = pressed_digit() {
+            let seed = self.seed.unwrap_or(0).checked_mul(10);
+            // Слишком длинное зерно не помещается в u64, и лишние цифры не вводятся.
+            if let Some(seed) = seed.and_then(|seed| seed.checked_add(digit)) {
+                self.seed = Some(seed);
+            }
+        }
+    }
+
+    /// Изменение мёртвой зоны стика на `delta` в пределах от 0 до наибольшей.
+    fn change_dead_zone(&mut self, delta: f32) {
+        let dead_zone = self.settings.dead_zone + delta;
+        self.settings.dead_zone = dead_zone.clamp(0.0, Settings::MAX_DEAD_ZONE);
+    }
+
+    /// Нажата ли клавиша старта, кнопка старта на геймпаде или было ли касание экрана.
+    fn is_start_pressed(&self) -> bool {
+        // На сенсорном экране клавиатуры нет, поэтому старт - это любое касание.
+        let touched = touches()
+            .iter()
+            .any(|touch| touch.phase == TouchPhase::Started);
+        is_key_pressed(self.key_bindings.start) || self.gamepad.is_start_pressed() || touched
+    }
+
+    /// Нажата ли клавиша паузы или кнопка паузы на геймпаде.
+    fn is_pause_pressed(&self) -> bool {
+        is_key_pressed(self.key_bindings.pause) || self.gamepad.is_pause_pressed()
+    }
+
+    /// Логика обновления меню паузы с выбранным пунктом `selected`.
+    fn update_pause_menu(&mut self, mut selected: usize) {
+        // Клавишами вверх и вниз выбираем пункт.
+        let arrows = Arrows::read(&self.gamepad);
+        if arrows.up {
+            selected = selected.saturating_sub(1);
+        }
+        if arrows.down {
+            selected = (selected + 1).min(PauseItem::ALL.len() - 1);
+        }
+        if let Screen::Paused {
+            selected: current, ..
+        } = &mut self.screen
+        {
+            *current = selected;
+        }
+
+        if !self.is_start_pressed() {
+            return;
+        }
+        match PauseItem::ALL[selected] {
+            PauseItem::Resume => self.toggle_pause(),
+            // Начатая игра бросается без подведения итогов, и начинается такая же новая.
+            // Обычная игра начинается заново на том же уровне сложности, без его выбора.
+            PauseItem::Restart => {
+                self.screen = Screen::Menu;
+                if matches!(self.started, MenuItem::Play) {
+                    self.screen = Screen::Playing(Box::new(self.new_game()));
+                } else {
+                    self.select_menu_item(self.started);
+                }
+            }
+            PauseItem::End => {
+                if let Screen::Paused { game, .. } = &mut self.screen {
+                    let stats = game.finish();
+                    self.finish_game(stats);
+                }
+            }
+        }
+    }
+
+    /// Постановка игры на паузу или снятие с неё.
+    fn toggle_pause(&mut self) {
+        // Временно забираем экран, чтобы переместить игру из одного варианта в другой.
+        self.screen = match std::mem::replace(&mut self.screen, Screen::Menu) {
+            Screen::Playing(mut game) => {
+                game.pause();
+                Screen::Paused { game, selected: 0 }
+            }
+            Screen::Paused { mut game, .. } => {
+                game.resume();
+                Screen::Playing(game)
+            }
+            screen => screen,
+        };
+    }
+
+    /// Завершение игры с итогами `stats`.
+    fn finish_game(&mut self, stats: RunStats) {
+        // Забираем игру с экрана.
+        let game = match std::mem::replace(&mut self.screen, Screen::Menu) {
+            Screen::Playing(game) | Screen::Paused { game, .. } => game,
+            _ => return,
+        };
+
+        // Просмотр записи и тренировка не влияют ни на рекорд, ни на сохранённую запись,
+        // и после них мы сразу возвращаемся в меню.
+        if game.is_playback() || game.is_practice() {
+            return;
+        }
+        // Игры с заданным зерном можно повторить, поэтому путь лучшей из них
+        // сохраняем как призрак. Игры с новым зерном не повторяются, и призрак им не нужен.
+        if self.seed.is_some() || stats.daily.is_some() {
+            let track = game.track();
+            let better = self
+                .ghost
+                .as_ref()
+                .is_none_or(|ghost| ghost.seed() != track.seed() || track.time() > ghost.time());
+            if better {
+                track.save();
+                self.ghost = Some(track.clone());
+            }
+        }
+        game.into_replay().save(); // Сохраняем запись сыгранной игры.
+
+        // Учитываем игру в общей статистике.
+        self.stats.record(&stats);
+        self.stats.save();
+
+        // Испытание засчитывается дню, в который оно началось, даже если закончилось после полуночи.
+        // Зерно испытания у всех одно, поэтому его результат учитывается отдельно
+        // от рекорда и таблицы результатов.
+        if let Some(date) = stats.daily {
+            let new_record = self.daily.record(date, stats.time);
+            if new_record {
+                self.daily.save();
+            }
+            self.screen = Screen::GameOver { stats, new_record };
+            return;
+        }
+
+        // Результат режима на время учитывается отдельно от рекорда и таблицы результатов.
+        if stats.goal.is_some() {
+            let new_record = self.time_attack.record(&stats);
+            if new_record {
+                self.time_attack.save();
+            }
+            self.screen = Screen::GameOver { stats, new_record };
+            return;
+        }
+
+        // Рекорд у каждого уровня сложности свой.
+        let best_time = &mut self.best_times[stats.difficulty.index()];
+        let new_record = stats.time > *best_time;
+        if new_record {
+            // Если новое время дольше рекордного,
+            *best_time = stats.time; // то обновляем рекорд
+            save_best_times(&self.best_times); // и сохраняем его.
+        }
+
+        self.best_score = self.best_score.max(stats.score);
+
+        // Если результат попадает в таблицу, то сначала спрашиваем инициалы.
+        self.screen = if self.leaderboard.qualifies(stats.time) {
+            Screen::NewEntry {
+                stats,
+                new_record,
+                initials: *b"AAA",
+                cursor: 0,
+            }
+        } else {
+            Screen::GameOver { stats, new_record }
+        };
+    }
+
+    /// Выбор уровня сложности с индексом `selected` перед началом игры.
+    /// Выбранный уровень запоминается в настройках.
+    fn update_choose_difficulty(&mut self, mut selected: usize, arrows: Arrows) {
+        if arrows.up {
+            selected = selected.saturating_sub(1);
+        }
+        if arrows.down {
+            selected = (selected + 1).min(Difficulty::ALL.len() - 1);
+        }
+        self.screen = Screen::ChooseDifficulty { selected };
+
+        if self.is_start_pressed() {
+            self.settings.difficulty = Difficulty::ALL[selected];
+            self.settings.save();
+            self.demo = Game::demo(self.settings.difficulty, self.config);
+            self.screen = Screen::Playing(Box::new(self.new_game()));
+        }
+    }
+
+    /// Начало воспроизведения записи `replay`.
+    fn watch_replay(&mut self, replay: Replay) {
+        let sounds = self.sounds.with_volume(self.settings.volume);
+        let game = Game::from_replay(replay, self.config, self.key_bindings, sounds);
+        self.started = MenuItem::Replay;
+        self.screen = Screen::Playing(Box::new(game));
+    }
+
+    /// Добавление результата с введёнными инициалами в таблицу и переход к итогам игры.
+    fn add_entry(&mut self) {
+        if let Screen::NewEntry {
+            stats,
+            new_record,
+            initials,
+            ..
+        } = self.screen
+        {
+            self.leaderboard.insert(Entry {
+                initials: String::from_utf8_lossy(&initials).into_owned(),
+                time: stats.time,
+                date: daily::today(),
+            });
+            self.leaderboard.save();
+            self.screen = Screen::GameOver { stats, new_record };
+        }
+    }
+
+    /// Цветовая тема, выбранная в настройках.
+    pub fn theme(&self) -> Theme {
+        self.settings.theme()
+    }
+
+    /// Отображение приложения.
+    pub fn draw(&self) {
+        let lang = self.settings.lang;
+        let theme = self.theme();
+        match &self.screen {
+            Screen::Menu => {
+                self.demo
+                    .draw(self.best_time(self.settings.difficulty), lang, &theme);
+                self.draw_menu();
+            }
+            Screen::Options { selected } => self.draw_options(*selected),
+            Screen::ChooseDifficulty { selected } => self.draw_choose_difficulty(*selected),
+            Screen::Playing(game) => game.draw(self.best_time(game.difficulty()), lang, &theme),
+            Screen::Paused { game, selected } => {
+                game.draw(self.best_time(game.difficulty()), lang, &theme);
+                // Поверх игры отображаем надпись о паузе и меню паузы.
+                draw_centered_text(lang.text(TextKey::Paused), 60.0, 0.0, theme.text);
+                for (index, item) in PauseItem::ALL.iter().enumerate() {
+                    let text = lang.text(item.name());
+                    let offset = 60.0 + 32.0 * index as f32;
+                    // Выбранный пункт выделяем цветом и стрелками.
+                    if index == *selected {
+                        draw_centered_text(&format!("< {} >", text), 28.0, offset, theme.selection);
+                    } else {
+                        draw_centered_text(text, 28.0, offset, theme.text);
+                    }
+                }
+            }
+            Screen::Leaderboard => self.draw_leaderboard(),
+            Screen::Stats => self.draw_stats(),
+            Screen::NewEntry {
+                stats,
+                initials,
+                cursor,
+                ..
+            } => self.draw_new_entry(stats, initials, *cursor),
+            Screen::GameOver { stats, new_record } => self.draw_game_over(stats, *new_record),
+        }
+    }
+
+    /// Отображение меню: пункты друг под другом, выбранный выделен.
+    fn draw_menu(&self) {
+        let lang = self.settings.lang;
+        let theme = self.theme();
+        for (index, item) in MenuItem::ALL.iter().enumerate() {
+            let text = match item {
+                MenuItem::Play => lang.text(TextKey::MenuPlay).to_string(),
+                MenuItem::Practice => lang.text(TextKey::MenuPractice).to_string(),
+                // Лучшее время берём за сегодня: после полуночи испытание начинается заново.
+                MenuItem::Daily => {
+                    let best = format!("{:.2}", self.daily.best(daily::today()));
+                    lang.format(TextKey::MenuDaily, best)
+                }
+                MenuItem::TimeAttack => {
+                    lang.format(TextKey::MenuTimeAttack, self.time_attack_best())
+                }
+                MenuItem::Replay => lang.text(TextKey::MenuReplay).to_string(),
+                MenuItem::Options => lang.text(TextKey::MenuOptions).to_string(),
+                MenuItem::Leaderboard => lang.text(TextKey::MenuLeaderboard).to_string(),
+                MenuItem::Stats => lang.text(TextKey::MenuStats).to_string(),
+                #[cfg(not(target_arch = "wasm32"))]
+                MenuItem::Quit => lang.text(TextKey::MenuQuit).to_string(),
+            };
+            let offset = -150.0 + 32.0 * index as f32;
+            // Выбранный пункт выделяем цветом и стрелками.
+            if index == self.menu.selected {
+                draw_centered_text(&format!("< {} >", text), 32.0, offset, theme.selection);
+            } else {
+                draw_centered_text(&text, 28.0, offset, theme.text);
+            }
+        }
+
+        let mode = lang.text(self.flight_mode.name());
+        let text = format!("{}: {}", lang.text(TextKey::FlightModeHint), mode);
+        draw_centered_text(&text, 24.0, 160.0, theme.text);
+        let volume = self.settings.volume * 100.0;
+        let text = format!("{}: {:.0}%", lang.text(TextKey::VolumeHint), volume);
+        draw_centered_text(&text, 24.0, 190.0, theme.text);
+
+        let start = format!("{:?}", self.key_bindings.start);
+        let text = lang.format(TextKey::MenuHelp, start);
+        draw_centered_text(&text, 20.0, 240.0, theme.text);
+    }
+
+    /// Рекордное время на уровне сложности `difficulty`.
+    fn best_time(&self, difficulty: Difficulty) -> f64 {
+        self.best_times[difficulty.index()]
+    }
+
+    /// Лучший результат режима на время: достигнута ли цель и с каким запасом жизней
+    /// или сколько удалось продержаться из необходимого времени.
+    fn time_attack_best(&self) -> String {
+        let best = self.time_attack;
+        if best.reached {
+            self.settings
+                .lang
+                .format(TextKey::ReachedWithLives, best.lives)
+        } else {
+            format!("{:.2} / {:.0}", best.time, time_attack::GOAL)
+        }
+    }
+
+    /// Отображение таблицы лучших результатов.
+    fn draw_leaderboard(&self) {
+        let lang = self.settings.lang;
+        let theme = self.theme();
+        draw_centered_text(lang.text(TextKey::Leaderboard), 40.0, -180.0, theme.text);
+
+        let entries = self.leaderboard.entries();
+        if entries.is_empty() {
+            draw_centered_text(lang.text(TextKey::NoResults), 24.0, -120.0, theme.text);
+        }
+        for (place, entry) in entries.iter().enumerate() {
+            let mut text = format!("{:>2}. {}  {:>8.2}", place + 1, entry.initials, entry.time);
+            if entry.date != 0 {
+                text += &format!("  {}", daily::format_date(entry.date));
+            }
+            draw_centered_text(&text, 24.0, -130.0 + 28.0 * place as f32, theme.text);
+        }
+
+        draw_centered_text(lang.text(TextKey::LeaderboardBack), 24.0, 180.0, theme.text);
+    }
+
+    /// Отображение общей статистики по всем играм.
+    fn draw_stats(&self) {
+        let lang = self.settings.lang;
+        let theme = self.theme();
+        draw_centered_text(lang.text(TextKey::Stats), 40.0, -180.0, theme.text);
+
+        let lines = [
+            (TextKey::GamesPlayed, self.stats.games_played.to_string()),
+            (TextKey::TotalTime, format!("{:.2}", self.stats.total_time)),
+            (
+                TextKey::AverageTime,
+                format!("{:.2}", self.stats.average_time()),
+            ),
+            (TextKey::TotalDodged, self.stats.dodged.to_string()),
+            (TextKey::TotalDestroyed, self.stats.destroyed.to_string()),
+        ];
+        for (index, (name, value)) in lines.iter().enumerate() {
+            let text = format!("{}: {}", lang.text(*name), value);
+            draw_centered_text(&text, 24.0, -120.0 + 32.0 * index as f32, theme.text);
+        }
+
+        draw_centered_text(lang.text(TextKey::StatsBack), 24.0, 180.0, theme.text);
+    }
+
+    /// Отображение экрана настроек с выбранным пунктом `selected`.
+    fn draw_options(&self, selected: usize) {
+        let lang = self.settings.lang;
+        let theme = self.theme();
+        draw_centered_text(lang.text(TextKey::Options), 40.0, -260.0, theme.text);
+
+        for (index, item) in OptionItem::ALL.iter().enumerate() {
+            let (name, value) = match item {
+                OptionItem::Volume => (
+                    TextKey::Volume,
+                    format!("{:.0}%", self.settings.volume * 100.0),
+                ),
+                OptionItem::Difficulty => (
+                    TextKey::Difficulty,
+                    lang.text(self.settings.difficulty.name()).to_string(),
+                ),
+                OptionItem::Language => (TextKey::Language, lang.name().to_string()),
+                OptionItem::Edges => {
+                    let edges = if self.settings.wrap_edges {
+                        TextKey::EdgesWrap
+                    } else {
+                        TextKey::EdgesClamp
+                    };
+                    (TextKey::Edges, lang.text(edges).to_string())
+                }
+                OptionItem::Steering => {
+                    let steering = if self.settings.mouse_steering {
+                        TextKey::SteeringMouse
+                    } else {
+                        TextKey::SteeringKeyboard
+                    };
+                    (TextKey::Steering, lang.text(steering).to_string())
+                }
+                OptionItem::Theme => (
+                    TextKey::Theme,
+                    lang.text(self.settings.theme.name()).to_string(),
+                ),
+                OptionItem::Spawning => {
+                    let spawning = if self.settings.waves {
+                        TextKey::SpawningWaves
+                    } else {
+                        TextKey::SpawningStream
+                    };
+                    (TextKey::Spawning, lang.text(spawning).to_string())
+                }
+                OptionItem::Vertical => {
+                    let vertical = if self.settings.vertical_movement {
+                        TextKey::On
+                    } else {
+                        TextKey::Off
+                    };
+                    (TextKey::Vertical, lang.text(vertical).to_string())
+                }
+                OptionItem::DeadZone => (
+                    TextKey::DeadZone,
+                    format!("{:.0}%", self.settings.dead_zone * 100.0),
+                ),
+                OptionItem::Curve => (
+                    TextKey::Curve,
+                    lang.text(self.settings.curve.name()).to_string(),
+                ),
+                OptionItem::Shapes => {
+                    let shapes = if self.settings.shapes {
+                        TextKey::On
+                    } else {
+                        TextKey::Off
+                    };
+                    (TextKey::Shapes, lang.text(shapes).to_string())
+                }
+                OptionItem::FrameLimit => {
+                    let limit = match self.settings.frame_limit {
+                        0 => lang.text(TextKey::Off).to_string(),
+                        limit => format!("{} fps", limit),
+                    };
+                    (TextKey::FrameLimit, limit)
+                }
+                OptionItem::Seed => {
+                    let seed = match self.seed {
+                        Some(seed) => seed.to_string(),
+                        None => lang.text(TextKey::SeedRandom).to_string(),
+                    };
+                    (TextKey::Seed, seed)
+                }
+            };
+            let text = format!("{}: {}", lang.text(name), value);
+            let offset = -232.0 + 20.0 * index as f32;
+            // Выбранный пункт выделяем цветом и стрелками.
+            if index == selected {
+                draw_centered_text(&format!("< {} >", text), 24.0, offset, theme.selection);
+            } else {
+                draw_centered_text(&text, 24.0, offset, theme.text);
+            }
+        }
+
+        // Подсказки с назначением клавиш.
+        draw_centered_text(lang.text(TextKey::Controls), 24.0, 36.0, theme.text);
+        for (index, (action, key)) in self.key_bindings.actions().iter().enumerate() {
+            let text = format!("{}: {:?}", lang.text(*action), key);
+            draw_centered_text(&text, 20.0, 60.0 + 20.0 * index as f32, theme.text);
+        }
+
+        draw_centered_text(lang.text(TextKey::OptionsHelp), 20.0, 260.0, theme.text);
+    }
+
+    /// Отображение итогов игры.
+    fn draw_game_over(&self, stats: &RunStats, new_record: bool) {
+        let lang = self.settings.lang;
+        let theme = self.theme();
+        let title = if stats.won {
+            TextKey::GoalReached
+        } else {
+            TextKey::GameOver
+        };
+        draw_centered_text(lang.text(title), 60.0, -100.0, theme.text);
+
+        let text = format!("{}: {:.2}", lang.text(TextKey::YourTime), stats.time);
+        draw_centered_text(&text, 32.0, -40.0, theme.text);
+
+        // В режиме на время и в ежедневном испытании рекорд свой.
+        if new_record {
+            draw_centered_text(lang.text(TextKey::NewRecord), 32.0, 0.0, theme.record);
+        } else if let Some(date) = stats.daily {
+            let text = format!(
+                "{}: {:.2}",
+                lang.text(TextKey::BestTime),
+                self.daily.best(date)
+            );
+            draw_centered_text(&text, 32.0, 0.0, theme.text);
+        } else if stats.goal.is_some() {
+            let text = format!("{}: {}", lang.text(TextKey::Best), self.time_attack_best());
+            draw_centered_text(&text, 32.0, 0.0, theme.text);
+        } else {
+            let best_time = self.best_time(stats.difficulty);
+            let text = format!("{}: {:.2}", lang.text(TextKey::BestTime), best_time);
+            draw_centered_text(&text, 32.0, 0.0, theme.text);
+        }
+
+        // В режиме волн рядом показываем и число пройденных волн.
+        let mut text = format!("{}: {}", lang.text(TextKey::Dodged), stats.dodged);
+        if let Some(waves) = stats.waves_cleared {
+            text += &format!(", {}: {}", lang.text(TextKey::WavesCleared), waves);
+        }
+        draw_centered_text(&text, 32.0, 40.0, theme.text);
+
+        let text = format!(
+            "{}: {} ({}: {})",
+            lang.text(TextKey::Score),
+            stats.score,
+            lang.text(TextKey::Best),
+            self.best_score
+        );
+        draw_centered_text(&text, 32.0, 80.0, theme.text);
+
+        // Зерно показываем, чтобы игру можно было повторить, задав его в настройках.
+        let text = format!("{}: {}", lang.text(TextKey::Seed), stats.seed);
+        draw_centered_text(&text, 20.0, 110.0, theme.text);
+
+        let start = format!("{:?}", self.key_bindings.start);
+        let text = lang.format(TextKey::ReturnToMenu, start);
+        draw_centered_text(&text, 24.0, 140.0, theme.text);
+        // В браузере записи не сохраняются, и смотреть нечего.
+        #[cfg(not(target_arch = "wasm32"))]
+        draw_centered_text(lang.text(TextKey::WatchReplay), 24.0, 170.0, theme.text);
+    }
+
+    /// Отображение выбора уровня сложности: уровни с их рекордами друг под другом,
+    /// выбранный с индексом `selected` выделен.
+    fn draw_choose_difficulty(&self, selected: usize) {
+        let lang = self.settings.lang;
+        let theme = self.theme();
+        draw_centered_text(
+            lang.text(TextKey::ChooseDifficulty),
+            40.0,
+            -150.0,
+            theme.text,
+        );
+
+        for (index, &difficulty) in Difficulty::ALL.iter().enumerate() {
+            let text = format!(
+                "{} ({}: {:.2})",
+                lang.text(difficulty.name()),
+                lang.text(TextKey::Best),
+                self.best_time(difficulty)
+            );
+            let offset = -60.0 + 40.0 * index as f32;
+            if index == selected {
+                draw_centered_text(&format!("< {} >", text), 32.0, offset, theme.selection);
+            } else {
+                draw_centered_text(&text, 28.0, offset, theme.text);
+            }
+        }
+
+        let start = format!("{:?}", self.key_bindings.start);
+        let text = lang.format(TextKey::ChooseDifficultyHelp, start);
+        draw_centered_text(&text, 24.0, 140.0, theme.text);
+    }
+
+    /// Отображение ввода инициалов для результата `stats`.
+    /// Изменяемая буква с индексом `cursor` выделена скобками.
+    fn draw_new_entry(&self, stats: &RunStats, initials: &[u8; 3], cursor: usize) {
+        let lang = self.settings.lang;
+        let theme = self.theme();
+        draw_centered_text(lang.text(TextKey::NewHighScore), 48.0, -100.0, theme.text);
+
+        let text = format!("{}: {:.2}", lang.text(TextKey::YourTime), stats.time);
+        draw_centered_text(&text, 32.0, -50.0, theme.text);
+
+        let letters: String = initials
+            .iter()
+            .enumerate()
+            .map(|(index, &letter)| {
+                let letter = letter as char;
+                if index == cursor {
+                    format!("[{}]", letter)
+                } else {
+                    format!(" {} ", letter)
+                }
+            })
+            .collect();
+        draw_centered_text(&letters, 48.0, 20.0, theme.selection);
+
+        let start = format!("{:?}", self.key_bindings.start);
+        let text = lang.format(TextKey::InitialsHelp, start);
+        draw_centered_text(&text, 20.0, 90.0, theme.text);
+    }
+}
+
+/// Рекордное время на каждом уровне сложности, в порядке [`Difficulty::ALL`].
+type BestTimes = [f64; Difficulty::ALL.len()];
+
+/// Файл, в котором хранится рекордное время.
+const BEST_TIME_FILE: &str = "best_time.txt";
+
+/// Загрузка рекордного времени на каждом уровне сложности.
+/// Если файла нет, то рекорды нулевые, а повреждённые строки пропускаются.
+fn load_best_times() -> BestTimes {
+    let mut times = [0.0; Difficulty::ALL.len()];
+    let text = match storage::read(BEST_TIME_FILE) {
+        Some(text) => text,
+        None => return times,
+    };
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if parse_best_time(line, &mut times).is_none() {
+            warn!("Failed to parse best time line: {}", line);
+        }
+    }
+    times
+}
+
+/// Разбор строки файла рекордов вида `normal = 12.5` в `times`. Строка из одного
+/// числа - рекорд из прежней версии, в которой уровни сложности не различались,
+/// и он засчитывается обычному уровню.
+fn parse_best_time(line: &str, times: &mut BestTimes) -> Option<()> {
+    let (difficulty, time) = match line.split_once('=') {
+        Some((key, time)) => (Difficulty::from_key(key.trim())?, time),
+        None => (Difficulty::Normal, line),
+    };
+    let time = time.trim().parse::<f64>().ok()?;
+    if !time.is_finite() || time < 0.0 {
+        return None;
+    }
+    times[difficulty.index()] = time;
+    Some(())
+}
+
+/// Сохранение рекордного времени строками вида `normal = 12.5`.
+/// Ошибка записи не прерывает игру.
+fn save_best_times(times: &BestTimes) {
+    let text: String = Difficulty::ALL
+        .iter()
+        .map(|difficulty| format!("{} = {}\n", difficulty.key(), times[difficulty.index()]))
+        .collect();
+    if let Err(err) = storage::write(BEST_TIME_FILE, &text) {
+        warn!("Failed to save best time: {}", err);
+    }
+}
+
+/// Цифра, нажатая в этом кадре на основной или цифровой клавиатуре.
+fn pressed_digit() -> Option<u64> {
+    const DIGITS: [(KeyCode, KeyCode); 10] = [
+        (KeyCode::Key0, KeyCode::Kp0),
+        (KeyCode::Key1, KeyCode::Kp1),
+        (KeyCode::Key2, KeyCode::Kp2),
+        (KeyCode::Key3, KeyCode::Kp3),
+        (KeyCode::Key4, KeyCode::Kp4),
+        (KeyCode::Key5, KeyCode::Kp5),
+        (KeyCode::Key6, KeyCode::Kp6),
+        (KeyCode::Key7, KeyCode::Kp7),
+        (KeyCode::Key8, KeyCode::Kp8),
+        (KeyCode::Key9, KeyCode::Kp9),
+    ];
+    (0..)
+        .zip(DIGITS)
+        .find(|&(_, (key, keypad))| is_key_pressed(key) || is_key_pressed(keypad))
+        .map(|(digit, _)| digit)
+}
+
+/// Запись для воспроизведения из файла, указанного в командной строке (`--replay replay.bin`).
+#[cfg(not(target_arch = "wasm32"))]
+fn replay_from_args() -> Option<Replay> {
+    let path = std::env::args()
+        .skip_while(|arg| arg != "--replay")
+        .nth(1)?;
+    Replay::load_from(&path)
+}
+
+/// В браузере командной строки и файловой системы нет.
+#[cfg(target_arch = "wasm32")]
+fn replay_from_args() -> Option<Replay> {
+    None
+}
+
+/// Зерно генератора случайных чисел из аргументов командной строки (`--seed 42`).
+fn seed_from_args() -> Option<u64> {
+    let mut args = std::env::args().skip_while(|arg| arg != "--seed").skip(1);
+    let seed = args.next()?;
+    match seed.parse() {
+        Ok(seed) => Some(seed),
+        Err(err) => {
+            warn!("Failed to parse seed {:?}: {}", seed, err);
+            None
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_times_are_kept_per_difficulty() {
+        let mut times = [0.0; Difficulty::ALL.len()];
+        // Рекорд из прежней версии засчитывается обычному уровню.
+        assert!(parse_best_time("12.5", &mut times).is_some());
+        assert!(parse_best_time("insane = 3", &mut times).is_some());
+        assert!(parse_best_time("extreme = 3", &mut times).is_none());
+        assert!(parse_best_time("hard = -1", &mut times).is_none());
+        assert_eq!(times, [0.0, 12.5, 0.0, 3.0]);
+    }
+}
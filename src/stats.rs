@@ -0,0 +1,101 @@
+//! Общая статистика по всем сыгранным играм.
+
+use crate::{game::RunStats, storage};
+use macroquad::prelude::*;
+use serde::Serialize;
+
+/// Файл, в котором хранится статистика.
+const STATS_FILE: &str = "stats.json";
+
+/// Повреждённый файл статистики копируется сюда, чтобы его не затёрло сохранение.
+const CORRUPT_STATS_FILE: &str = "stats.json.bak";
+
+/// Общая статистика, накопленная за все игры.
+#[derive(Default, Serialize)]
+pub struct TotalStats {
+    /// Число сыгранных игр.
+    pub games_played: u64,
+    /// Суммарное время всех игр в секундах.
+    pub total_time: f64,
+    /// Число астероидов, пролетевших мимо корабля.
+    pub dodged: u64,
+    /// Число уничтоженных астероидов.
+    pub destroyed: u64,
+}
+
+impl TotalStats {
+    /// Учёт завершённой игры с итогами `run`.
+    pub fn record(&mut self, run: &RunStats) {
+        self.games_played += 1;
+        self.total_time += run.time;
+        self.dodged += run.dodged as u64;
+        self.destroyed += run.destroyed as u64;
+    }
+
+    /// Средняя длительность игры. Пока игр не было, она равна нулю.
+    pub fn average_time(&self) -> f64 {
+        if self.games_played == 0 {
+            0.0
+        } else {
+            self.total_time / self.games_played as f64
+        }
+    }
+
+    /// Загрузка статистики из файла. Если файла нет, то статистика нулевая.
+    ///
+    /// Отдельные повреждённые или отсутствующие значения считаются нулевыми, а остальные
+    /// сохраняются. Если файл не удалось разобрать целиком, то он копируется в
+    /// [`CORRUPT_STATS_FILE`], чтобы его можно было восстановить вручную.
+    pub fn load() -> Self {
+        let text = match storage::read(STATS_FILE) {
+            Some(text) => text,
+            None => return Self::default(), // Файла нет - игр ещё не было.
+        };
+        let value = match serde_json::from_str::<serde_json::Value>(&text) {
+            Ok(serde_json::Value::Object(value)) => value,
+            Ok(_) | Err(_) => {
+                warn!(
+                    "Failed to parse stats, copying them to {}",
+                    CORRUPT_STATS_FILE
+                );
+                if let Err(err) = storage::write(CORRUPT_STATS_FILE, &text) {
+                    warn!("Failed to copy corrupt stats: {}", err);
+                }
+                return Self::default();
+            }
+        };
+
+        // Целое значение поля `name` или ноль, если его нет или оно повреждено.
+        let count = |name: &str| {
+            let count = value.get(name).and_then(serde_json::Value::as_u64);
+            if count.is_none() {
+                warn!("Failed to parse stats field {}", name);
+            }
+            count.unwrap_or(0)
+        };
+        let total_time = value
+            .get("total_time")
+            .and_then(serde_json::Value::as_f64)
+            .filter(|time| time.is_finite() && *time >= 0.0);
+        if total_time.is_none() {
+            warn!("Failed to parse stats field total_time");
+        }
+
+        Self {
+            games_played: count("games_played"),
+            total_time: total_time.unwrap_or(0.0),
+            dodged: count("dodged"),
+            destroyed: count("destroyed"),
+        }
+    }
+
+    /// Сохранение статистики в файл. Ошибка записи не прерывает игру.
+    pub fn save(&self) {
+        let result = serde_json::to_string_pretty(self)
+            .map_err(|err| err.to_string())
+            .and_then(|text| storage::write(STATS_FILE, &text).map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            warn!("Failed to save stats: {}", err);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Хранилище сохранений игры: рекордов, статистики, настроек и записей.
+//!
+//! На компьютере каждое сохранение - файл в каталоге настроек игры. В браузере
+//! сохранения хранятся в его локальном хранилище (Web Storage), если игра собрана
+//! с флагом `web-storage`, а без него не сохраняются.
+
+use macroquad::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+/// Путь к файлу `name` в каталоге настроек игры, например `~/.config/asteroids`
+/// в Linux или `%APPDATA%\asteroids\config` в Windows. Если каталог профиля
+/// пользователя определить не удалось, файл хранится в текущем каталоге.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn path(name: &str) -> PathBuf {
+    match directories::ProjectDirs::from("", "", "asteroids") {
+        Some(dirs) => dirs.config_dir().join(name),
+        None => PathBuf::from(name),
+    }
+}
+
+/// Текст сохранения `name`. Если его нет или его не удалось прочитать, то `None`.
+pub fn read(name: &str) -> Option<String> {
+    let bytes = read_bytes(name)?;
+    match String::from_utf8(bytes) {
+        Ok(text) => Some(text),
+        Err(err) => {
+            warn!("Failed to read {}: {}", name, err);
+            None
+        }
+    }
+}
+
+/// Запись текста `contents` в сохранение `name`.
+pub fn write(name: &str, contents: &str) -> std::io::Result<()> {
+    write_bytes(name, contents.as_bytes())
+}
+
+/// Содержимое файла `name` из каталога настроек (см. [`path`]).
+///
+/// Раньше сохранения лежали в текущем каталоге. Если в каталоге настроек файла
+/// ещё нет, он читается из текущего каталога и копируется в каталог настроек,
+/// так что старый файл читается только один раз.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_bytes(name: &str) -> Option<Vec<u8>> {
+    let path = path(name);
+    match std::fs::read(&path) {
+        Ok(bytes) => return Some(bytes),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => {
+            warn!("Failed to read {}: {}", path.display(), err);
+            return None;
+        }
+    }
+
+    let bytes = std::fs::read(name).ok()?;
+    if let Err(err) = write_bytes(name, &bytes) {
+        warn!("Failed to move {} to {}: {}", name, path.display(), err);
+    }
+    Some(bytes)
+}
+
+/// Запись `contents` в файл `name` в каталоге настроек (см. [`path`]).
+/// Каталог создаётся, если его ещё нет.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_bytes(name: &str, contents: &[u8]) -> std::io::Result<()> {
+    let path = path(name);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, contents)
+}
+
+/// Ключ сохранения `name` в локальном хранилище браузера. Хранилище общее для всех
+/// страниц сайта, поэтому ключи начинаются с названия игры.
+#[cfg(all(target_arch = "wasm32", feature = "web-storage"))]
+fn key(name: &str) -> String {
+    format!("asteroids/{}", name)
+}
+
+/// Сохранение `name` из локального хранилища браузера. В нём хранится только текст.
+#[cfg(all(target_arch = "wasm32", feature = "web-storage"))]
+fn read_bytes(name: &str) -> Option<Vec<u8>> {
+    quad_storage_sys::get(&key(name)).map(String::into_bytes)
+}
+
+/// Запись сохранения `name` в локальное хранилище браузера. В нём хранится только
+/// текст, поэтому `contents` должно быть в UTF-8.
+#[cfg(all(target_arch = "wasm32", feature = "web-storage"))]
+fn write_bytes(name: &str, contents: &[u8]) -> std::io::Result<()> {
+    let text = std::str::from_utf8(contents)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+    quad_storage_sys::set(&key(name), text);
+    Ok(())
+}
+
+/// Без флага `web-storage` в браузере сохранений нет.
+#[cfg(all(target_arch = "wasm32", not(feature = "web-storage")))]
+fn read_bytes(_name: &str) -> Option<Vec<u8>> {
+    None
+}
+
+/// Без флага `web-storage` в браузере сохранения не записываются.
+#[cfg(all(target_arch = "wasm32", not(feature = "web-storage")))]
+fn write_bytes(_name: &str, _contents: &[u8]) -> std::io::Result<()> {
+    Ok(())
+}
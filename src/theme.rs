@@ -0,0 +1,139 @@
+//! Цветовые темы.
+
+use crate::lang::TextKey;
+use macroquad::prelude::*;
+
+/// Цвета фона, корабля, астероидов и текста.
+#[derive(Clone, Copy)]
+pub struct Theme {
+    /// Различаются ли объекты формой, а не только цветом: усиления отображаются квадратами,
+    /// преследователи - треугольниками, а бронированные астероиды - с кольцами.
+    /// Выбирается в настройках отдельно от цветов.
+    pub shapes: bool,
+    /// Цвет фона.
+    pub background: Color,
+    /// Цвет корабля.
+    pub ship: Color,
+    /// Цвет целого астероида.
+    pub asteroid: Color,
+    /// Цвет контура астероида.
+    pub asteroid_outline: Color,
+    /// Цвет основного текста.
+    pub text: Color,
+    /// Цвет выбранного пункта меню, вводимых инициалов и обратного отсчёта.
+    pub selection: Color,
+    /// Цвет нового рекорда.
+    pub record: Color,
+}
+
+/// Готовая цветовая тема, выбираемая в настройках.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ThemePreset {
+    /// Тёмно-серый фон и светлые астероиды.
+    Dark,
+    /// Светлый фон и тёмные астероиды.
+    Light,
+    /// Чёрный фон и яркие цвета.
+    Neon,
+    /// Чёрный фон, белые астероиды и жёлтый корабль для плохо различающих цвета.
+    Contrast,
+}
+
+impl ThemePreset {
+    /// Следующая тема (для переключения в настройках).
+    pub fn next(self) -> Self {
+        match self {
+            ThemePreset::Dark => ThemePreset::Light,
+            ThemePreset::Light => ThemePreset::Neon,
+            ThemePreset::Neon => ThemePreset::Contrast,
+            ThemePreset::Contrast => ThemePreset::Dark,
+        }
+    }
+
+    /// Предыдущая тема (для переключения в настройках).
+    pub fn previous(self) -> Self {
+        match self {
+            ThemePreset::Dark => ThemePreset::Contrast,
+            ThemePreset::Light => ThemePreset::Dark,
+            ThemePreset::Neon => ThemePreset::Light,
+            ThemePreset::Contrast => ThemePreset::Neon,
+        }
+    }
+
+    /// Название темы для отображения.
+    pub fn name(self) -> TextKey {
+        match self {
+            ThemePreset::Dark => TextKey::ThemeDark,
+            ThemePreset::Light => TextKey::ThemeLight,
+            ThemePreset::Neon => TextKey::ThemeNeon,
+            ThemePreset::Contrast => TextKey::ThemeContrast,
+        }
+    }
+
+    /// Название темы в файле настроек.
+    pub fn key(self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "dark",
+            ThemePreset::Light => "light",
+            ThemePreset::Neon => "neon",
+            ThemePreset::Contrast => "contrast",
+        }
+    }
+
+    /// Тема по её названию в файле, см. [`ThemePreset::key`].
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "dark" => Some(ThemePreset::Dark),
+            "light" => Some(ThemePreset::Light),
+            "neon" => Some(ThemePreset::Neon),
+            "contrast" => Some(ThemePreset::Contrast),
+            _ => None,
+        }
+    }
+
+    /// Цвета темы. Различение объектов по форме выключено.
+    pub fn theme(self) -> Theme {
+        match self {
+            ThemePreset::Dark => Theme {
+                shapes: false,
+                background: DARKGRAY,
+                ship: WHITE,
+                asteroid: LIGHTGRAY,
+                asteroid_outline: GRAY,
+                text: BLACK,
+                selection: WHITE,
+                record: GREEN,
+            },
+            ThemePreset::Light => Theme {
+                shapes: false,
+                background: Color::new(0.93, 0.91, 0.86, 1.0),
+                ship: DARKBLUE,
+                asteroid: GRAY,
+                asteroid_outline: DARKGRAY,
+                text: BLACK,
+                selection: MAROON,
+                record: DARKGREEN,
+            },
+            ThemePreset::Neon => Theme {
+                shapes: false,
+                background: BLACK,
+                ship: Color::new(0.0, 1.0, 1.0, 1.0),
+                asteroid: VIOLET,
+                asteroid_outline: MAGENTA,
+                text: Color::new(0.2, 1.0, 0.4, 1.0),
+                selection: YELLOW,
+                record: PINK,
+            },
+            ThemePreset::Contrast => Theme {
+                shapes: false,
+                background: BLACK,
+                ship: YELLOW,
+                asteroid: WHITE,
+                asteroid_outline: GRAY,
+                text: WHITE,
+                selection: YELLOW,
+                record: Color::new(0.0, 1.0, 1.0, 1.0),
+            },
+        }
+    }
+}
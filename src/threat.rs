@@ -0,0 +1,70 @@
+//! Ближайшая угроза кораблю: астероид, который раньше всех врежется в корабль,
+//! если ни он, ни корабль не изменят скорости.
+
+use macroquad::prelude::*;
+
+/// Астероиды, которые врежутся позже, угрозой не считаются.
+pub const HORIZON: f32 = 2.0;
+
+/// Через сколько секунд круг, отстоящий от корабля на `offset` и движущийся относительно
+/// него со скоростью `velocity`, подойдёт к нему на расстояние `reach`.
+/// Если круг уже так близко, то ноль, а если он пролетит мимо или удаляется, то `None`.
+pub fn time_to_impact(offset: Vec2, velocity: Vec2, reach: f32) -> Option<f32> {
+    let distance = offset.length_squared() - reach * reach;
+    if distance <= 0.0 {
+        return Some(0.0);
+    }
+    // Решаем |offset + velocity * t| = reach относительно t и берём меньший корень.
+    let a = velocity.length_squared();
+    let half_b = offset.dot(velocity);
+    let discriminant = half_b * half_b - a * distance;
+    if half_b >= 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    Some((-half_b - discriminant.sqrt()) / a)
+}
+
+/// Отображение указателя у корабля с центром `ship`, направленного на угрозу в `target`,
+/// которая врежется через `time` секунд. Чем ближе столкновение, тем указатель ярче.
+pub fn draw_indicator(ship: Vec2, target: Vec2, time: f32) {
+    /// Расстояние от центра корабля до указателя.
+    const DISTANCE: f32 = 40.0;
+    const SIZE: f32 = 8.0;
+
+    let direction = (target - ship).normalize_or_zero();
+    if direction == Vec2::ZERO {
+        return;
+    }
+    let side = direction.perp() * SIZE * 0.6;
+    let base = ship + direction * DISTANCE;
+    let tip = base + direction * SIZE;
+    let alpha = (1.0 - time / HORIZON).clamp(0.3, 1.0);
+    let color = Color::new(RED.r, RED.g, RED.b, alpha);
+    draw_triangle(tip, base - side, base + side, color);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_to_impact_of_approaching_circle() {
+        // Летит прямо на корабль: расстояние 100, из них 20 уже занимают радиусы.
+        let time = time_to_impact(Vec2::new(0.0, -100.0), Vec2::new(0.0, 40.0), 20.0);
+        assert_eq!(time, Some(2.0));
+        // Удаляется или пролетает мимо.
+        assert_eq!(
+            time_to_impact(Vec2::new(0.0, -100.0), Vec2::new(0.0, -40.0), 20.0),
+            None
+        );
+        assert_eq!(
+            time_to_impact(Vec2::new(50.0, -100.0), Vec2::new(0.0, 40.0), 20.0),
+            None
+        );
+        // Уже задевает корабль.
+        assert_eq!(
+            time_to_impact(Vec2::new(0.0, 10.0), Vec2::ZERO, 20.0),
+            Some(0.0)
+        );
+    }
+}
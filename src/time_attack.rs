@@ -0,0 +1,115 @@
+//! Режим на время: нужно продержаться заданное время на наибольшей сложности.
+//!
+//! Лучший результат в этом режиме хранится отдельно от рекорда обычной игры:
+//! сначала важно, достигнута ли цель, затем - сколько жизней при этом осталось,
+//! а если цель не достигнута - сколько удалось продержаться.
+
+use crate::difficulty::Difficulty;
+use crate::game::RunStats;
+use crate::storage;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Файл, в котором хранится лучший результат режима на время.
+const TIME_ATTACK_FILE: &str = "time_attack.json";
+
+/// Время, которое нужно продержаться, в секундах.
+pub const GOAL: f64 = 60.0;
+
+/// Уровень сложности режима на время.
+pub const DIFFICULTY: Difficulty = Difficulty::Hard;
+
+/// Лучший результат режима на время.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TimeAttackBest {
+    /// Достигнута ли цель.
+    pub reached: bool,
+    /// Сколько жизней осталось при достижении цели.
+    pub lives: u32,
+    /// Сколько удалось продержаться. Ноль, если режим ещё не проходился.
+    pub time: f64,
+}
+
+impl TimeAttackBest {
+    /// Учёт итогов игры `stats`. Возвращает, стали ли они лучшим результатом.
+    pub fn record(&mut self, stats: &RunStats) -> bool {
+        let result = Self {
+            reached: stats.won,
+            lives: if stats.won { stats.lives } else { 0 },
+            time: stats.time,
+        };
+        if !result.is_better_than(self) {
+            return false;
+        }
+        *self = result;
+        true
+    }
+
+    /// Лучше ли этот результат, чем `other`.
+    fn is_better_than(&self, other: &Self) -> bool {
+        match (self.reached, other.reached) {
+            (true, false) => true,
+            (false, true) => false,
+            (true, true) => self.lives > other.lives,
+            (false, false) => self.time > other.time,
+        }
+    }
+
+    /// Загрузка результата из файла. Если файла нет или он повреждён, то результата нет.
+    pub fn load() -> Self {
+        let text = match storage::read(TIME_ATTACK_FILE) {
+            Some(text) => text,
+            None => return Self::default(),
+        };
+        serde_json::from_str(&text).unwrap_or_else(|err| {
+            warn!("Failed to parse time attack best: {}", err);
+            Self::default()
+        })
+    }
+
+    /// Сохранение результата в файл. Ошибка записи не прерывает игру.
+    pub fn save(&self) {
+        let result = serde_json::to_string_pretty(self)
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                storage::write(TIME_ATTACK_FILE, &text).map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            warn!("Failed to save time attack best: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(time: f64, won: bool, lives: u32) -> RunStats {
+        RunStats {
+            time,
+            dodged: 0,
+            destroyed: 0,
+            waves_cleared: None,
+            score: 0,
+            goal: Some(GOAL),
+            won,
+            lives,
+            difficulty: DIFFICULTY,
+            daily: None,
+            seed: 0,
+        }
+    }
+
+    #[test]
+    fn reaching_goal_beats_surviving_longer() {
+        let mut best = TimeAttackBest::default();
+        assert!(best.record(&stats(30.0, false, 0)));
+        assert!(!best.record(&stats(20.0, false, 0)));
+        assert!(best.record(&stats(GOAL, true, 1)));
+        // Цель уже достигнута: лучше только с большим запасом жизней.
+        assert!(!best.record(&stats(45.0, false, 0)));
+        assert!(!best.record(&stats(GOAL, true, 1)));
+        assert!(best.record(&stats(GOAL, true, 3)));
+        assert_eq!(best.lives, 3);
+    }
+}
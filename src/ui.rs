@@ -0,0 +1,55 @@
+//! Вспомогательные функции отображения интерфейса.
+
+use macroquad::prelude::*;
+use std::sync::OnceLock;
+
+/// Файл шрифта. Стандартный шрифт macroquad не содержит кириллицы.
+const FONT_FILE: &str = "assets/DejaVuSans.ttf";
+
+/// Шрифт интерфейса. Если его не удалось загрузить, используется стандартный шрифт.
+static FONT: OnceLock<Font> = OnceLock::new();
+
+/// Загрузка шрифта интерфейса. Ошибка загрузки не прерывает игру.
+pub async fn load_font() {
+    let font = load_file(FONT_FILE)
+        .await
+        .map_err(|err| err.to_string())
+        .and_then(|bytes| load_ttf_font_from_bytes(&bytes).map_err(|err| err.0.to_string()));
+    match font {
+        Ok(font) => {
+            let _ = FONT.set(font);
+        }
+        Err(err) => warn!("Failed to load font {}: {}", FONT_FILE, err),
+    }
+}
+
+/// Отображение текста шрифтом интерфейса; `y` - положение базовой линии.
+pub fn draw_ui_text(text: &str, x: f32, y: f32, font_size: f32, color: Color) {
+    let params = TextParams {
+        font: FONT.get().copied().unwrap_or_default(),
+        font_size: font_size as _,
+        color,
+        ..Default::default()
+    };
+    draw_text_ex(text, x, y, params);
+}
+
+/// Размер текста, отображаемого шрифтом интерфейса.
+pub fn measure_ui_text(text: &str, font_size: f32) -> TextDimensions {
+    measure_text(text, FONT.get().copied(), font_size as _, 1.0)
+}
+
+/// Отображение текста по центру экрана, смещённого по вертикали на `offset`.
+pub fn draw_centered_text(text: &str, font_size: f32, offset: f32, color: Color) {
+    // Вычисляем, какой размер занимает текст на экране.
+    let text_size = measure_ui_text(text, font_size);
+
+    // Располагаем текст по центру.
+    let text_pos = (
+        (screen_width() - text_size.width) / 2.0,
+        (screen_height() - text_size.height) / 2.0 + offset,
+    );
+
+    // Отображаем текст
+    draw_ui_text(text, text_pos.0, text_pos.1, font_size, color);
+}
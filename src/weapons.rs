@@ -0,0 +1,54 @@
+//! Орудие корабля.
+
+use crate::bullet::Bullet;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Орудие, стреляющее пулями не чаще, чем позволяет перезарядка.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Weapons {
+    /// Время с последнего выстрела.
+    fire_timer: f64,
+}
+
+impl Weapons {
+    /// Минимальное время между выстрелами.
+    const FIRE_COOLDOWN: f64 = 0.25;
+
+    /// Перезарядка за время `elapsed_time` и выстрел, если `fire` установлен
+    /// и орудие перезарядилось. Пуля вылетает из точки `position` в направлении `direction`.
+    pub fn update(
+        &mut self,
+        elapsed_time: f64,
+        fire: bool,
+        position: Vec2,
+        direction: Vec2,
+    ) -> Option<Bullet> {
+        self.fire_timer += elapsed_time;
+        if !fire || self.fire_timer <= Self::FIRE_COOLDOWN {
+            return None;
+        }
+        self.fire_timer = 0.0;
+        Some(Bullet::new(position, direction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fires_only_after_cooldown() {
+        let mut weapons = Weapons::default();
+        let fire = |weapons: &mut Weapons, elapsed_time| {
+            weapons
+                .update(elapsed_time, true, Vec2::ZERO, Vec2::new(0.0, -1.0))
+                .is_some()
+        };
+        assert!(fire(&mut weapons, Weapons::FIRE_COOLDOWN * 2.0));
+        assert!(!fire(&mut weapons, Weapons::FIRE_COOLDOWN / 2.0));
+        // Пока клавиша не нажата, орудие перезаряжается, но не стреляет.
+        assert!(weapons.update(1.0, false, Vec2::ZERO, Vec2::ZERO).is_none());
+        assert!(fire(&mut weapons, 0.0));
+    }
+}
@@ -0,0 +1,157 @@
+//! Проверка того, что кадр игры с пулом астероидов не выделяет память в долгой игре.
+//!
+//! Отдельный исполняемый файл нужен, чтобы считающий аллокации распределитель памяти
+//! не подменял распределитель в остальных тестах.
+
+use asteroids::asteroid::Asteroid;
+use asteroids::config::GameConfig;
+use asteroids::difficulty::{Difficulty, Progression};
+use asteroids::game::Game;
+use asteroids::input::Input;
+use asteroids::replay::FrameInput;
+use asteroids::rng::Rng;
+use asteroids::ship::FlightMode;
+use macroquad::prelude::Vec2;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Распределитель памяти, считающий аллокации в каждом потоке отдельно,
+/// чтобы параллельно идущие тесты не мешали подсчёту.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Число аллокаций, сделанных в текущем потоке при выполнении `f`.
+fn count_allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+/// Кадров до начала подсчёта: за это время пул и прочие буферы игры дорастают
+/// до наибольшего нужного размера.
+const WARM_UP_STEPS: u32 = 20_000;
+/// Кадров, в которых считаются аллокации.
+const STEPS: u32 = 20_000;
+/// Размер окна игры.
+fn screen_size() -> Vec2 {
+    Vec2::new(800.0, 600.0)
+}
+
+/// Управление в кадре `step`: корабль переходит от края к краю и стреляет
+/// каждый десятый кадр, так что астероиды и раскалываются, и пролетают мимо.
+fn input(step: u32) -> FrameInput {
+    let input = Input {
+        steer: ((step % 1000 / 200) % 3) as f32 - 1.0,
+        fire: step.is_multiple_of(10),
+        ..Input::default()
+    };
+    FrameInput {
+        input,
+        elapsed_time: Game::STEP,
+    }
+}
+
+/// Число аллокаций за `STEPS` кадров игры после разгона.
+fn pooled_allocations() -> usize {
+    let mut game = Game::simulation(
+        FlightMode::Classic,
+        false,
+        Difficulty::Normal,
+        GameConfig::default(),
+        42,
+        screen_size(),
+    );
+    let mut step = |step: u32| {
+        assert!(
+            game.advance(input(step)).is_none(),
+            "the ship was destroyed"
+        );
+    };
+    (0..WARM_UP_STEPS).for_each(&mut step);
+    let allocations = count_allocations(|| (WARM_UP_STEPS..WARM_UP_STEPS + STEPS).for_each(step));
+    assert!(game.finish().destroyed > 0, "no asteroid was split");
+    allocations
+}
+
+/// Число аллокаций за `STEPS` кадров прежнего пути, в котором астероиды хранились
+/// в `Vec`: новые добавлялись через `push`, а улетевшие забывались через `retain`,
+/// осколки собирались в новый `Vec`, и астероиды рядом с кораблём - тоже.
+/// Астероиды появляются и раскалываются примерно так же часто, как в игре.
+fn vec_allocations() -> usize {
+    let config = GameConfig::default();
+    let ship = screen_size() / 2.0;
+    let mut rng = Rng::new(42);
+    let mut asteroids = Vec::with_capacity(100);
+    let mut step = |step: u32| {
+        if step.is_multiple_of(10) {
+            let asteroid = Asteroid::new(
+                &mut rng,
+                Difficulty::Normal,
+                Progression::START,
+                &config,
+                screen_size().x,
+            );
+            asteroids.push(asteroid);
+        }
+        let mut fragments = Vec::new();
+        if step.is_multiple_of(7) && !asteroids.is_empty() {
+            let asteroid = asteroids.swap_remove(0);
+            fragments.extend(asteroid.fragment(&mut rng, &config));
+        }
+        asteroids.extend(fragments);
+        for asteroid in &mut asteroids {
+            asteroid.update(Game::STEP, 100.0);
+        }
+        asteroids.retain(|asteroid| asteroid.position().y < screen_size().y * 2.0);
+        let near: Vec<usize> = (0..asteroids.len())
+            .filter(|&index| (asteroids[index].position() - ship).length() < screen_size().y)
+            .collect();
+        std::hint::black_box(near);
+    };
+    (0..WARM_UP_STEPS).for_each(&mut step);
+    count_allocations(|| (WARM_UP_STEPS..WARM_UP_STEPS + STEPS).for_each(step))
+}
+
+/// Долгая игра: астероиды появляются, раскалываются и улетают за нижний край.
+/// Прежний путь выделял память почти в каждом кадре, а с пулом, переиспользуемым
+/// буфером осколков и поиском по сетке без выделения памяти кадр обходится без неё.
+/// Редкие аллокации остаются только у записей игры, растущих вместе с ней.
+#[test]
+fn pooled_steps_allocate_far_less_than_vec_churn() {
+    let pooled = pooled_allocations();
+    let churn = vec_allocations();
+    assert!(
+        churn >= STEPS as usize,
+        "the baseline allocated only {} times",
+        churn
+    );
+    assert!(
+        pooled * 100 <= churn,
+        "{} allocations in {} steps, the Vec path made {}",
+        pooled,
+        STEPS,
+        churn
+    );
+}